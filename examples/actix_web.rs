@@ -0,0 +1,34 @@
+use actix_web::{get, App, HttpServer, Responder};
+use rand::Rng;
+
+use rust_html::*;
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    HttpServer::new(|| App::new().service(root))
+        .bind(("0.0.0.0", 3000))?
+        .run()
+        .await
+}
+
+#[get("/")]
+async fn root() -> impl Responder {
+    let random_number: i32 = rand::thread_rng().gen_range(0..100);
+    let page = rhtml! {r#"
+        <!DOCTYPE html>
+        <html lang="en">
+            <head>
+                <title>rust_html actix-web</title>
+            </head>
+            <body>
+                <h1>Welcome to rust_html!</h1>
+                <p>
+                    Here's a random number: {random_number}
+                    <br/>
+                    Refresh to get another one.
+                </p>
+            </body>
+        </html>
+    "#};
+    page
+}
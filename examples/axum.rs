@@ -18,7 +18,7 @@ async fn root() -> impl IntoResponse {
     let random_number: i32 = rand::thread_rng().gen_range(0..100);
     let page = rhtml! {r#"
         <!DOCTYPE html>
-        <html>
+        <html lang="en">
             <head>
                 <title>rust_html axum</title>
             </head>
@@ -1,5 +1,4 @@
 use axum::{response::IntoResponse, routing::get, Router};
-use rand::Rng;
 
 use rust_html::*;
 
@@ -68,7 +67,9 @@ fn calculator() -> Template {
     // Since we trust our javascript we feel safe to use Unescaped!
     let trusted_javascript = Unescaped(CALCULATOR_JS.to_string());
 
-    // The main html component
+    // The main html component, with the scripts and styles it needs
+    // in the page head. They are included once, however many
+    // calculators are on the page.
     rhtml! { r#"
         <div class="calculator" x-data="{trusted_javascript}">
             <h2 x-text="value" class="display">
@@ -81,26 +82,25 @@ fn calculator() -> Template {
             </div>
         </div>
     "# }
+    .with_head(HeadItem::DeferredScript("//unpkg.com/alpinejs".to_string()))
+    .with_head(HeadItem::Script("//unpkg.com/mathjs".to_string()))
+    .with_head(HeadItem::Style(CSS_STYLE))
 }
 
 /// The main endpoint for our calculator app
 async fn root() -> impl IntoResponse {
-    let stylesheet = Unescaped(CSS_STYLE.to_string());
+    let content = calculator();
     let page = rhtml! {r#"
         <!DOCTYPE html>
-        <html>
+        <html lang="en">
             <head>
                 <title>rust_html calculator</title>
-                <script src="//unpkg.com/alpinejs" defer></script>
-                <script src="//unpkg.com/mathjs"></script>
-                <style>
-                    {stylesheet}
-                </style>
+                {content.head()}
             </head>
             <body>
                 <h1>Calculator</h1>
                 <h2>rust_html + alpine.js</h2>
-                {calculator()}
+                {content}
             </body>
         </html>
     "#};
@@ -109,7 +109,7 @@ async fn root() -> impl IntoResponse {
 
 // -------- Javascript below ----------
 
-const CALCULATOR_JS: &'static str = r#"
+const CALCULATOR_JS: &str = r#"
 {
     value: '',
     insert(value) {
@@ -137,7 +137,7 @@ const CALCULATOR_JS: &'static str = r#"
 
 // -------- Stylesheet below ----------
 
-const CSS_STYLE: &'static str = r#"
+const CSS_STYLE: &str = r#"
 
 html {
     text-align: center;
@@ -24,7 +24,7 @@ fn card_group() -> Template {
 fn layout(title: &str, content: impl Render) -> Template {
     rhtml! { r#"
         <!DOCTYPE html>
-        <html>
+        <html lang="en">
             <head>
                 <title>{title}</title>
             </head>
@@ -0,0 +1,60 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::{Digest, Sha384};
+
+/// URL path that fingerprinted assets are served from
+const ASSET_PATH: &str = "/assets";
+
+/// Number of hash bytes in the file name of an asset
+const FINGERPRINT_BYTES: usize = 5;
+
+/// Fingerprinted URL and subresource integrity of a static file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub url: String,
+    pub integrity: String,
+}
+
+/// Hashes the contents of a file, e.g. `static/app.js` is
+/// served as `/assets/app.0123456789.js`
+pub fn fingerprint(path: &str, contents: &[u8]) -> Fingerprint {
+    let hash = Sha384::digest(contents);
+    let fingerprint: String = hash[..FINGERPRINT_BYTES]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let file_name = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => {
+            format!("{}.{}.{}", stem, fingerprint, extension)
+        }
+        _ => format!("{}.{}", file_name, fingerprint),
+    };
+    Fingerprint {
+        url: format!("{}/{}", ASSET_PATH, file_name),
+        integrity: format!("sha384-{}", STANDARD.encode(hash)),
+    }
+}
+
+#[cfg(test)]
+mod test_asset {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        let fingerprint = fingerprint("static/app.js", b"abc");
+        assert_eq!(fingerprint.url, "/assets/app.cb00753f45.js");
+        assert_eq!(
+            fingerprint.integrity,
+            "sha384-ywB1P0WjXou1oD1pmsZQBycsMqsO3tFjGotgWkP/W+2AhgcroefMI1i67KE0yCWn"
+        );
+    }
+
+    #[test]
+    fn test_file_names() {
+        let url = |path: &str| fingerprint(path, b"abc").url;
+        assert_eq!(url("app.min.js"), "/assets/app.min.cb00753f45.js");
+        assert_eq!(url("static\\css\\site.css"), "/assets/site.cb00753f45.css");
+        assert_eq!(url("static/LICENSE"), "/assets/LICENSE.cb00753f45");
+        assert_eq!(url("static/.htaccess"), "/assets/.htaccess.cb00753f45");
+    }
+}
@@ -0,0 +1,14 @@
+use std::path::Path;
+
+use proc_macro2::TokenStream;
+use rust_html_parser::{read_config, Config};
+
+use crate::util::compile_error;
+
+/// Reads the config of the crate being compiled. This isn't cached
+/// between expansions, since a long-running macro server like
+/// rust-analyzer would otherwise keep using an outdated config.
+pub fn load() -> Result<Config, TokenStream> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    read_config(Path::new(&manifest_dir)).map_err(|error| compile_error(&error))
+}
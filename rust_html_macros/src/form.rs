@@ -0,0 +1,438 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, Fields, Lit, LitStr, UnOp};
+
+/// Validation attributes accepted in `#[form(...)]` on fields
+const VALIDATION_ATTRIBUTES: [&str; 7] = [
+    "min",
+    "max",
+    "step",
+    "minlength",
+    "maxlength",
+    "pattern",
+    "autocomplete",
+];
+
+/// Derives `rust_html::form::Form` for structs with named fields,
+/// or `rust_html::form::FormField` for enums with unit variants
+pub fn derive_form(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = match syn::parse2(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error(),
+    };
+    let result = match &input.data {
+        Data::Struct(data) => derive_struct(&input, data),
+        Data::Enum(data) => derive_enum(&input, data),
+        Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "Form can only be derived for structs and enums",
+        )),
+    };
+    result.unwrap_or_else(|err| err.to_compile_error())
+}
+
+fn derive_struct(input: &DeriveInput, data: &DataStruct) -> syn::Result<TokenStream> {
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Form can only be derived for structs with named fields",
+        ));
+    };
+    let rename_all = serde_attributes(&input.attrs)?.rename_all;
+    let mut rendered_fields = vec![];
+    for field in &fields.named {
+        let form = form_attributes(&field.attrs, false)?;
+        let serde = serde_attributes(&field.attrs)?;
+        if form.skip || serde.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let ident_name = ident.to_string();
+        let ident_name = ident_name.trim_start_matches("r#");
+        let name = match serde.rename {
+            Some(name) => name,
+            None => match &rename_all {
+                Some(rule) => rename_field(ident_name, rule),
+                None => ident_name.to_string(),
+            },
+        };
+        let label = form.label.unwrap_or_else(|| humanize(ident_name));
+        let input_type = optional_literal(form.input_type);
+        let placeholder = optional_literal(form.placeholder);
+        let required = match form.required {
+            Some(required) => quote! { Some(#required) },
+            None => quote! { None },
+        };
+        let (attribute_names, attribute_values): (Vec<_>, Vec<_>) =
+            form.attributes.into_iter().unzip();
+        let ty = &field.ty;
+        rendered_fields.push(quote! {
+            rust_html::form::render_field::<#ty>(
+                &rust_html::form::FieldSpec {
+                    name: #name,
+                    label: #label,
+                    input_type: #input_type,
+                    placeholder: #placeholder,
+                    required: #required,
+                    attributes: &[#((#attribute_names, #attribute_values)),*],
+                },
+                values.map(|values| &values.#ident),
+                errors,
+            )
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics rust_html::form::Form for #ident #ty_generics #where_clause {
+            fn form_fields(
+                values: Option<&Self>,
+                errors: &rust_html::form::FormErrors,
+            ) -> rust_html::Template {
+                let fields: Vec<rust_html::Template> = vec![#(#rendered_fields),*];
+                rust_html::Render::render(&rust_html::TemplateGroup(fields))
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream> {
+    let rename_all = serde_attributes(&input.attrs)?.rename_all;
+    let mut variant_idents = vec![];
+    let mut values = vec![];
+    let mut labels = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "Form can only be derived for enums with unit variants",
+            ));
+        }
+        let form = form_attributes(&variant.attrs, true)?;
+        let serde = serde_attributes(&variant.attrs)?;
+        if form.skip || serde.skip {
+            continue;
+        }
+        let ident_name = variant.ident.to_string();
+        let value = match serde.rename {
+            Some(value) => value,
+            None => match &rename_all {
+                Some(rule) => rename_variant(&ident_name, rule),
+                None => ident_name.clone(),
+            },
+        };
+        labels.push(form.label.unwrap_or_else(|| humanize(&ident_name)));
+        values.push(value);
+        variant_idents.push(&variant.ident);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics rust_html::form::FormField for #ident #ty_generics #where_clause {
+            fn select_options() -> Option<&'static [(&'static str, &'static str)]> {
+                Some(&[#((#values, #labels)),*])
+            }
+
+            #[allow(unreachable_patterns)]
+            fn form_value(&self) -> String {
+                match self {
+                    #(Self::#variant_idents => #values.to_string(),)*
+                    _ => String::new(),
+                }
+            }
+        }
+    })
+}
+
+/// Settings from `#[form(...)]` attributes
+#[derive(Debug, Default)]
+struct FormAttributes {
+    label: Option<String>,
+    placeholder: Option<String>,
+    input_type: Option<String>,
+    required: Option<bool>,
+    skip: bool,
+    /// Validation attributes, by name
+    attributes: Vec<(String, String)>,
+}
+
+fn form_attributes(attrs: &[Attribute], variant: bool) -> syn::Result<FormAttributes> {
+    let mut form = FormAttributes::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("form")) {
+        attr.parse_nested_meta(|meta| {
+            let key = meta
+                .path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+            match key.as_str() {
+                "label" => form.label = Some(meta.value()?.parse::<LitStr>()?.value()),
+                "skip" => form.skip = true,
+                _ if variant => return Err(meta.error("expected `label` or `skip`")),
+                "placeholder" => form.placeholder = Some(meta.value()?.parse::<LitStr>()?.value()),
+                "input_type" => form.input_type = Some(meta.value()?.parse::<LitStr>()?.value()),
+                "required" => form.required = Some(true),
+                "optional" => form.required = Some(false),
+                key if VALIDATION_ATTRIBUTES.contains(&key) => {
+                    form.attributes
+                        .push((key.to_string(), literal_value(&meta)?));
+                }
+                _ => {
+                    return Err(meta.error(format!(
+                        "expected `label`, `placeholder`, `input_type`, `required`, \
+                         `optional`, `skip` or one of {}",
+                        VALIDATION_ATTRIBUTES.join(", ")
+                    )))
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok(form)
+}
+
+/// Value of a validation attribute, a string or (negative) number literal
+fn literal_value(meta: &ParseNestedMeta) -> syn::Result<String> {
+    let expr: Expr = meta.value()?.parse()?;
+    let (negative, lit) = match &expr {
+        Expr::Lit(lit) => (false, &lit.lit),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => match &*unary.expr {
+            Expr::Lit(lit) => (true, &lit.lit),
+            _ => return Err(syn::Error::new_spanned(&expr, "expected a literal")),
+        },
+        _ => return Err(syn::Error::new_spanned(&expr, "expected a literal")),
+    };
+    let value = match lit {
+        Lit::Str(lit) if !negative => lit.value(),
+        Lit::Int(lit) => lit.base10_digits().to_string(),
+        Lit::Float(lit) => lit.base10_digits().to_string(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &expr,
+                "expected a string or number",
+            ))
+        }
+    };
+    Ok(if negative {
+        format!("-{}", value)
+    } else {
+        value
+    })
+}
+
+/// The serde attributes that decide the names in a submitted form
+#[derive(Debug, Default)]
+struct SerdeAttributes {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    skip: bool,
+}
+
+fn serde_attributes(attrs: &[Attribute]) -> syn::Result<SerdeAttributes> {
+    let mut serde = SerdeAttributes::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                serde.rename = deserialize_name(&meta)?.or(serde.rename.take());
+            } else if meta.path.is_ident("rename_all") {
+                serde.rename_all = deserialize_name(&meta)?.or(serde.rename_all.take());
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                serde.skip = true;
+            } else {
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(serde)
+}
+
+/// Name in `rename = "..."` or `rename(deserialize = "...")`
+fn deserialize_name(meta: &ParseNestedMeta) -> syn::Result<Option<String>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse::<LitStr>()?.value()));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("deserialize") {
+            name = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else {
+            skip_meta(&meta)?;
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+/// Skips the value or nested list of a serde attribute
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|meta| skip_meta(&meta))?;
+    }
+    Ok(())
+}
+
+fn optional_literal(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+/// Field or variant name as a label, e.g. `first_name` or `FirstName` to "First name".
+/// Acronyms keep their case, `HTTPStatus` becomes "HTTP status".
+fn humanize(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1);
+        // A new word starts at `Name` in `firstName`, and at `Status` in `HTTPStatus`
+        let boundary = c.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase())
+            });
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    words.extend((!word.is_empty()).then_some(word));
+
+    let mut label = String::new();
+    for (i, word) in words.iter().enumerate() {
+        let is_acronym = word.chars().count() > 1 && word.chars().all(|c| !c.is_lowercase());
+        if i > 0 {
+            label.push(' ');
+        }
+        if is_acronym {
+            label.push_str(word);
+        } else if i == 0 {
+            let mut chars = word.chars();
+            label.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+            label.extend(chars.flat_map(char::to_lowercase));
+        } else {
+            label.push_str(&word.to_lowercase());
+        }
+    }
+    label
+}
+
+/// Applies serde's `rename_all` to a snake_case field name
+fn rename_field(name: &str, rule: &str) -> String {
+    let pascal_case = || -> String {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect()
+    };
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => pascal_case(),
+        "camelCase" => lowercase_first(&pascal_case()),
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// Applies serde's `rename_all` to a PascalCase variant name
+fn rename_variant(name: &str, rule: &str) -> String {
+    let snake_case = || -> String {
+        let mut snake = String::new();
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        }
+        snake
+    };
+    match rule {
+        "lowercase" => name.to_ascii_lowercase(),
+        "UPPERCASE" => name.to_ascii_uppercase(),
+        "camelCase" => lowercase_first(name),
+        "snake_case" => snake_case(),
+        "SCREAMING_SNAKE_CASE" => snake_case().to_ascii_uppercase(),
+        "kebab-case" => snake_case().replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake_case().to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+fn lowercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_lowercase().chain(chars).collect::<String>())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test_form {
+    use super::*;
+
+    #[test]
+    fn test_humanize() {
+        assert_eq!(humanize("name"), "Name");
+        assert_eq!(humanize("first_name"), "First name");
+        assert_eq!(humanize("FirstName"), "First name");
+        assert_eq!(humanize("_private"), "Private");
+        assert_eq!(humanize("HTTPStatus"), "HTTP status");
+        assert_eq!(humanize("userID"), "User ID");
+        assert_eq!(humanize("http_status"), "Http status");
+        assert_eq!(humanize("api_URL"), "Api URL");
+    }
+
+    #[test]
+    fn test_rename_field() {
+        assert_eq!(rename_field("first_name", "camelCase"), "firstName");
+        assert_eq!(rename_field("first_name", "PascalCase"), "FirstName");
+        assert_eq!(rename_field("first_name", "kebab-case"), "first-name");
+        assert_eq!(
+            rename_field("first_name", "SCREAMING-KEBAB-CASE"),
+            "FIRST-NAME"
+        );
+        assert_eq!(rename_field("first_name", "snake_case"), "first_name");
+    }
+
+    #[test]
+    fn test_rename_variant() {
+        assert_eq!(rename_variant("SuperAdmin", "lowercase"), "superadmin");
+        assert_eq!(rename_variant("SuperAdmin", "snake_case"), "super_admin");
+        assert_eq!(rename_variant("SuperAdmin", "camelCase"), "superAdmin");
+        assert_eq!(rename_variant("SuperAdmin", "kebab-case"), "super-admin");
+        assert_eq!(rename_variant("SuperAdmin", "PascalCase"), "SuperAdmin");
+    }
+
+    #[test]
+    fn test_derive_errors() {
+        let error = |input: TokenStream| derive_form(input).to_string();
+        assert!(error(quote! { struct Point(i32, i32); }).contains("named fields"));
+        assert!(error(quote! { enum Shape { Circle(f32) } }).contains("unit variants"));
+        assert!(
+            error(quote! { struct User { #[form(size = 3)] name: String } })
+                .contains("expected `label`")
+        );
+        assert!(
+            error(quote! { enum Role { #[form(placeholder = "x")] Admin } })
+                .contains("expected `label` or `skip`")
+        );
+    }
+}
@@ -1,12 +1,20 @@
-use parse::compile_check_html;
+#![cfg_attr(feature = "nightly", feature(proc_macro_diagnostic))]
+
+use options::parse_input;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
+use rust_html_parser::{
+    prepare_html, split_placeholders, trim_whitespace_per_line, Placeholder, PreparedHtml,
+    TemplateOptions,
+};
+use xml::compile_check_xml;
 
-#[macro_use]
-extern crate html5ever;
-
-mod parse;
+mod asset;
+mod config;
+mod form;
+mod options;
 mod util;
+mod xml;
 
 use util::*;
 
@@ -84,6 +92,112 @@ use util::*;
 /// assert_eq!(String::from(page), "<div class=my_class></div>");
 /// ```
 ///
+/// Fragments that need a specific parent element, e.g. `<li>` or
+/// `<option>`, can declare it before the template with `in`:
+///
+/// ```rust
+/// use rust_html::rhtml;
+/// let item = rhtml! { in "ul", "<li>Item</li>" };
+/// assert_eq!(&String::from(item), "<li>Item</li>");
+/// ```
+///
+/// Elements the parser would insert implicitly are not allowed, so this
+/// example will not compile (a `<tr>` in a table gets an implicit `<tbody>`):
+///
+/// ```rust compile_fail
+/// use rust_html::rhtml;
+/// let row = rhtml! { in "table", "<tr><td>Cell</td></tr>" };
+/// ```
+///
+/// Placeholders are validated as if they rendered text, except between
+/// the children of elements like `<head>`, `<table>` and `<tr>` where they
+/// must render child elements. The following example will not compile,
+/// since text in a `<tr>` is moved out of the table:
+///
+/// ```rust compile_fail
+/// use rust_html::rhtml;
+/// let name = "Bob";
+/// let row = rhtml! { in "tbody", "<tr>Name: {name}<td>Cell</td></tr>" };
+/// ```
+///
+/// Templates must also be nested as written, the following example
+/// will not compile since `<div>` implicitly closes the `<p>` element.
+/// Use `allow(fixups)` before the template to accept such markup.
+///
+/// ```rust compile_fail
+/// use rust_html::rhtml;
+/// let page = rhtml! { "<p>Text<div>Block</div>" };
+/// ```
+///
+/// Templates are also checked for basic accessibility issues, like images
+/// without alt text or inputs without a label. These lints are warnings by
+/// default, and can be configured per template with `allow(...)`,
+/// `warn(...)` or `deny(...)` using a lint name or `a11y` for all of them:
+///
+/// ```rust compile_fail
+/// use rust_html::rhtml;
+/// let image = rhtml! { deny(a11y), r#"<img src="logo.png">"# };
+/// ```
+///
+/// A `<style scoped>` block only applies to the elements of its template,
+/// and is collected with `Template::scoped_css` instead of rendered:
+///
+/// ```rust
+/// use rust_html::rhtml;
+/// let note = rhtml! { "<style scoped>p {{ color: gray; }}</style><p>Note</p>" };
+/// assert!(String::from(note.scoped_css()).starts_with("p[data-rhtml-"));
+/// assert!(String::from(note).starts_with("<p data-rhtml-"));
+/// ```
+///
+/// Deprecated elements like `<center>`, inline event handlers and
+/// `target="_blank"` links without `rel="noopener"` are warnings, configured
+/// with `deprecated_elements`, `inline_handlers`, `target_blank` or
+/// `practices` for all of them. On stable Rust, warnings are reported as the
+/// use of a deprecated constant named after the lint. The `nightly` feature
+/// reports them as compiler warnings instead.
+///
+/// Duplicate ids and `for`, `aria-labelledby` or `href="#..."` references
+/// to missing ids are errors, configured with `duplicate_ids` and
+/// `dangling_ids` or `ids` for both:
+///
+/// ```rust compile_fail
+/// use rust_html::rhtml;
+/// let field = rhtml! { r#"<label for="nmae">Name</label><input id="name">"# };
+/// ```
+///
+/// The `unknown_names` lint is off by default. It checks element and
+/// attribute names against the HTML standard, allowing custom elements,
+/// `data-*`, `aria-*` and attributes starting with one of the `prefixes`:
+///
+/// ```rust
+/// use rust_html::rhtml;
+/// let button = rhtml! {
+///     deny(unknown_names),
+///     prefixes("hx-", "@"),
+///     r#"<button hx-post="/save" @click="saved = true">Save</button>"#
+/// };
+/// ```
+///
+/// ```rust compile_fail
+/// use rust_html::rhtml;
+/// let image = rhtml! { deny(unknown_names), r#"<img scr="logo.png" alt="Logo">"# };
+/// ```
+///
+/// With `minify(true)`, whitespace in text content is collapsed:
+///
+/// ```rust
+/// use rust_html::rhtml;
+/// let list = rhtml! { minify(true), "<ul>
+///     <li>One</li>
+///     <li>Two</li>
+/// </ul>" };
+/// assert_eq!(String::from(list), "<ul>\n<li>One</li>\n<li>Two</li>\n</ul>");
+/// ```
+///
+/// Defaults for lint levels, prefixes and `minify` can be set for a whole
+/// crate in `[package.metadata.rust_html]` in `Cargo.toml`, or in a
+/// `rust_html.toml` file next to it.
+///
 /// For more examples and documentation, check out the README.md
 ///
 #[proc_macro]
@@ -91,55 +205,351 @@ pub fn rhtml(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     expand(input.into()).into()
 }
 
+/// rust_html - Async templates
+///
+/// The rhtml_async macro works like `rhtml!`, but placeholders ending
+/// in `.await` may contain futures (or `AsyncRender` components).
+/// The macro returns an `AsyncTemplate` which renders all awaited
+/// placeholders concurrently when it is awaited itself.
+///
+/// ## Example
+///
+/// ```rust
+/// use rust_html::{rhtml, rhtml_async, Template};
+///
+/// async fn user_name(id: u32) -> String {
+///     format!("user {}", id)
+/// }
+///
+/// async fn page() -> Template {
+///     let title = "Users";
+///     rhtml_async! { r#"
+///         <h1>{title}</h1>
+///         <div>{user_name(1).await}</div>
+///         <div>{user_name(2).await}</div>
+///     "# }.await
+/// }
+/// ```
+///
+/// Placeholders without `.await` are rendered immediately,
+/// exactly like in the `rhtml!` macro.
+///
+#[proc_macro]
+pub fn rhtml_async(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_async(input.into()).into()
+}
+
+/// rust_html - XML templates
+///
+/// The rxml macro works like `rhtml!`, but validates the template as
+/// well-formed XML at compile time and escapes values for XML. It returns
+/// an `XmlTemplate`, e.g. for sitemaps and feeds. Whitespace before
+/// an XML declaration is removed, as it must start the document.
+/// Template options and the crate config only apply to HTML templates.
+///
+/// ```rust
+/// use rust_html::rxml;
+/// let title = "Fish & chips";
+/// let item = rxml! { "<item><title>{title}</title></item>" };
+/// assert_eq!(String::from(item), "<item><title>Fish &amp; chips</title></item>");
+/// ```
+///
+/// HTML templates inserted into an XML template are escaped as text,
+/// e.g. for the `<description>` of an RSS item.
+///
+/// The following example will not compile, since `&nbsp;`
+/// is not defined in XML:
+///
+/// ```rust compile_fail
+/// use rust_html::rxml;
+/// let item = rxml! { "<title>Hello&nbsp;world</title>" };
+/// ```
+///
+#[proc_macro]
+pub fn rxml(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_xml(input.into()).into()
+}
+
+/// rust_html - Templates from HTML files
+///
+/// The rhtml_file macro works like `rhtml!`, but reads the template from
+/// a file. The path is relative to the crate root (`CARGO_MANIFEST_DIR`),
+/// and placeholders in the file can use any variable in scope:
+///
+/// ```rust ignore
+/// use rust_html::rhtml_file;
+/// let title = "Hello!";
+/// let page = rhtml_file!("templates/page.html");
+/// ```
+///
+/// With the `hot-reload` feature, debug builds re-read the file each
+/// time the template is created. The file can then be edited without
+/// recompiling, as long as it only uses placeholders from the compiled
+/// version. Release builds always use the embedded template.
+///
+#[proc_macro]
+pub fn rhtml_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_file(input.into()).into()
+}
+
+/// rust_html - Fingerprinted static assets
+///
+/// The asset macro hashes a static file at compile time and returns an
+/// `Asset` with a cache-busting URL, like `/assets/app.0123456789.js`,
+/// and the subresource integrity of the file. The path is relative to the
+/// crate root (`CARGO_MANIFEST_DIR`) and the file is embedded in the binary:
+///
+/// ```rust ignore
+/// use rust_html::{asset, rhtml, Asset};
+/// const APP_JS: Asset = asset!("static/app.js");
+/// let script = rhtml! { r#"
+///     <script src="{APP_JS}" integrity="{APP_JS.integrity}" crossorigin="anonymous"></script>
+/// "# };
+/// ```
+///
+/// With the `assets` feature, `rust_html::assets::asset_router` serves
+/// the assets at their URLs with immutable caching headers. The
+/// `rust_html::assets` module has a complete, compiled example.
+///
+#[proc_macro]
+pub fn asset(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_asset(input.into()).into()
+}
+
+/// rust_html - Forms from structs
+///
+/// Derives `rust_html::form::Form` for a struct, rendering a labelled
+/// input for each field. The input type depends on the field type:
+/// strings are text inputs, numbers are number inputs, `bool` is a
+/// checkbox and `Option` fields are not required. Deriving `Form` for
+/// an enum with unit variants renders it as a `<select>`.
+///
+/// ```rust
+/// use rust_html::form::Form;
+/// use rust_html::Form;
+///
+/// #[derive(Form)]
+/// enum Plan {
+///     Free,
+///     #[form(label = "Pro (paid)")]
+///     Pro,
+/// }
+///
+/// #[derive(Form)]
+/// struct Signup {
+///     #[form(label = "Email address", input_type = "email", placeholder = "you@example.com")]
+///     email: String,
+///     #[form(minlength = 8, input_type = "password")]
+///     password: String,
+///     age: Option<u8>,
+///     plan: Plan,
+///     #[form(skip)]
+///     referrer: Option<String>,
+/// }
+///
+/// let html = String::from(Signup::empty_form());
+/// assert!(html.contains(r#"<label for="email">Email address</label>"#));
+/// assert!(html.contains(r#"minlength="8""#));
+/// ```
+///
+/// Fields accept `label`, `placeholder`, `input_type` (e.g. `email`,
+/// `date`, `textarea` or `hidden`), `required`, `optional`, `skip` and
+/// the validation attributes `min`, `max`, `step`, `minlength`,
+/// `maxlength`, `pattern` and `autocomplete` in `#[form(...)]`.
+/// Variants accept `label` and `skip`. Input names and option values
+/// follow serde's `rename` and `rename_all` attributes.
+///
+#[proc_macro_derive(Form, attributes(form))]
+pub fn derive_form(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    form::derive_form(input.into()).into()
+}
+
 /// Main macro implementation (using types from proc_macro2 crate)
 /// Parses input, generates the string template and list of rust evaluators
 /// to produce the final output TokenStream
 fn expand(input: TokenStream) -> TokenStream {
-    // Parse input string inside macro
-    let input_string = match validate_input(input) {
-        Ok(string) => string,
-        Err(err) => {
-            return err;
-        }
-    };
-
-    // Convert contents to html template and list of rust evaluators
-    let (html_parts, rust_evaluators) = match parse_rhtml(&input_string) {
+    let PreparedTemplate {
+        html_literals,
+        template_end_literal,
+        rust_evaluators,
+        placeholders,
+        warnings,
+        config_tracking,
+        ..
+    } = match prepare_template(input) {
         Ok(result) => result,
         Err(err) => return err,
     };
+    let template_parts_ident = format_ident!("template_parts");
+    let rendered: Vec<_> = rust_evaluators
+        .iter()
+        .zip(placeholders)
+        .map(|(evaluator, placeholder)| render_placeholder(evaluator, placeholder))
+        .collect();
 
-    // Compile time HTML syntax check
-    let html_for_validate = trim_whitespace_per_line(&html_parts.join(""));
-    if let Err(error) = compile_check_html(&html_for_validate) {
-        return error;
+    quote! {
+        {
+            #config_tracking
+            #(#warnings)*
+            let #template_parts_ident: Vec<(&'static str, rust_html::Template)> = vec![#(
+                (
+                    #html_literals,
+                    #rendered
+                )
+            ),*];
+            rust_html::Template::build_internal(
+                #template_parts_ident,
+                #template_end_literal
+            )
+        }
     }
+}
 
-    // Build output TokenStream
+/// Async macro implementation. Awaited placeholders are converted
+/// to `AsyncRender` futures, the rest are rendered immediately.
+fn expand_async(input: TokenStream) -> TokenStream {
+    let PreparedTemplate {
+        html_literals,
+        template_end_literal,
+        rust_evaluators,
+        placeholders,
+        warnings,
+        config_tracking,
+        ..
+    } = match prepare_template(input) {
+        Ok(result) => result,
+        Err(err) => return err,
+    };
     let template_parts_ident = format_ident!("template_parts");
-    let mut html_literals: Vec<_> = html_parts
+    let render_futures: Vec<_> = rust_evaluators
         .iter()
-        .map(|part| string_to_literal(part))
+        .zip(placeholders)
+        .map(|(evaluator, placeholder)| {
+            let future = render_future(evaluator, placeholder);
+            if placeholder == Placeholder::UrlAttribute {
+                quote! { async move { rust_html::Render::render(&rust_html::SafeUrl(#future.await)) } }
+            } else {
+                future
+            }
+        })
         .collect();
-    let Some(template_end_literal) = html_literals.pop() else {
-        return compile_error("internal");
+
+    quote! {
+        {
+            #config_tracking
+            #(#warnings)*
+            let #template_parts_ident: Vec<(&'static str, rust_html::RenderFuture<'_>)> = vec![#(
+                (
+                    #html_literals,
+                    Box::pin(#render_futures)
+                )
+            ),*];
+            rust_html::AsyncTemplate::build_internal(
+                #template_parts_ident,
+                #template_end_literal
+            )
+        }
+    }
+}
+
+/// XML macro implementation, like `expand` but
+/// with XML validation and XML escaping
+fn expand_xml(input: TokenStream) -> TokenStream {
+    let PreparedTemplate {
+        html_literals,
+        template_end_literal,
+        rust_evaluators,
+        ..
+    } = match validate_input(input)
+        .and_then(|xml| prepare_template_string(&xml, Syntax::Xml, &TemplateOptions::default()))
+    {
+        Ok(result) => result,
+        Err(err) => return err,
     };
+    let template_parts_ident = format_ident!("template_parts");
 
-    if html_literals.len() != rust_evaluators.len() {
-        return compile_error(
-            "unexpected number of parameters, this might be an internal rust_html error",
-        );
+    quote! {
+        {
+            let #template_parts_ident: Vec<(&'static str, rust_html::xml::XmlTemplate)> = vec![#(
+                (
+                    #html_literals,
+                    rust_html::xml::RenderXml::render_xml(&#rust_evaluators)
+                )
+            ),*];
+            rust_html::xml::XmlTemplate::build_internal(
+                #template_parts_ident,
+                #template_end_literal
+            )
+        }
     }
+}
+
+/// File macro implementation. Reads the template file and passes the
+/// placeholder source code along for hot reloading.
+fn expand_file(input: TokenStream) -> TokenStream {
+    let relative_path = match validate_input(input) {
+        Ok(path) => path,
+        Err(err) => return err,
+    };
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&manifest_dir).join(&relative_path);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) => {
+            let error = format!("failed to read template file '{}': {}", path.display(), err);
+            return compile_error(&error);
+        }
+    };
+    let config = match config::load() {
+        Ok(config) => config,
+        Err(err) => return err,
+    };
+    let PreparedTemplate {
+        html_literals,
+        template_end_literal,
+        rust_evaluators,
+        rust_sources,
+        placeholders,
+        warnings,
+        ..
+    } = match prepare_template_string(&content, Syntax::Html, &config.options) {
+        Ok(result) => result,
+        Err(err) => return err,
+    };
+    let config_tracking = track_file(config.path.as_deref());
+    let rendered: Vec<_> = rust_evaluators
+        .iter()
+        .zip(&placeholders)
+        .map(|(evaluator, placeholder)| render_placeholder(evaluator, *placeholder))
+        .collect();
+    let path_literal = string_to_literal(&path.to_string_lossy());
+    let manifest_dir_literal = string_to_literal(&manifest_dir);
+    let source_literals: Vec<_> = rust_sources
+        .iter()
+        .map(|source| string_to_literal(source.trim()))
+        .collect();
+    let in_attribute: Vec<_> = placeholders
+        .iter()
+        .map(|placeholder| *placeholder != Placeholder::Content)
+        .collect();
+    let template_parts_ident = format_ident!("template_parts");
 
     quote! {
         {
+            // Recompile when the template file changes
+            const _: &str = include_str!(#path_literal);
+            #config_tracking
+            #(#warnings)*
             let #template_parts_ident: Vec<(&'static str, rust_html::Template)> = vec![#(
                 (
                     #html_literals,
-                    rust_html::Render::render(&#rust_evaluators)
+                    #rendered
                 )
             ),*];
-            rust_html::Template::build_internal(
+            rust_html::Template::build_file_internal(
+                #path_literal,
+                #manifest_dir_literal,
+                &[#((#source_literals, #in_attribute)),*],
                 #template_parts_ident,
                 #template_end_literal
             )
@@ -147,102 +557,213 @@ fn expand(input: TokenStream) -> TokenStream {
     }
 }
 
-/// Parses rhtml content. On success returns HTML string template and list of
-/// rust token streams to inject into the string.
-fn parse_rhtml(input: &str) -> Result<(Vec<String>, Vec<TokenStream>), TokenStream> {
-    let mut skip_next = false;
-    let mut depth = 0;
-    let mut html_buffer: Vec<char> = vec![];
-    let mut rust_buffer: Vec<char> = vec![];
-
-    let mut html_parts: Vec<_> = vec![];
-    let mut rust_evaluators: Vec<_> = vec![];
-    let chars: Vec<_> = input.chars().collect();
-
-    for (i, token) in chars.iter().enumerate() {
-        if skip_next {
-            skip_next = false;
-            continue;
-        };
-
-        let peek = chars.get(i + 1);
-        let rust_mode = depth > 0;
-        match token {
-            '{' => {
-                // Escaping bracket
-                if !rust_mode && peek == Some(&'{') {
-                    html_buffer.push('{');
-                    skip_next = true;
-                    continue;
-                }
-                depth += 1;
-                if depth > 1 {
-                    rust_buffer.push('{');
-                }
-            }
-            '}' => {
-                // Escaping bracket
-                if !rust_mode && peek == Some(&'}') {
-                    html_buffer.push('}');
-                    skip_next = true;
-                    continue;
-                }
-                depth -= 1;
-                if depth > 0 {
-                    rust_buffer.push('}');
-                }
-                if depth < 0 {
-                    return Err(compile_error(
-                        "Unexpected close bracket '}', need an open bracket first (or '}}' to escape)"
-                    ));
-                }
-            }
-            token => {
-                if rust_mode {
-                    rust_buffer.push(*token);
-                } else {
-                    html_buffer.push(*token);
-                }
-            }
+/// Asset macro implementation. Hashes the file and embeds its contents.
+fn expand_asset(input: TokenStream) -> TokenStream {
+    let relative_path = match validate_input(input) {
+        Ok(path) => path,
+        Err(err) => return err,
+    };
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&manifest_dir).join(&relative_path);
+    let contents = match std::fs::read(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            let error = format!("failed to read asset '{}': {}", path.display(), err);
+            return compile_error(&error);
         }
+    };
+    let asset::Fingerprint { url, integrity } = asset::fingerprint(&relative_path, &contents);
+    let relative_path_literal = string_to_literal(&relative_path);
+    let path_literal = string_to_literal(&path.to_string_lossy());
+    let url_literal = string_to_literal(&url);
+    let integrity_literal = string_to_literal(&integrity);
 
-        let change_to_rust = !rust_mode && depth > 0;
-        let change_to_html = rust_mode && depth == 0;
-
-        // When exiting html, push html buffer
-        if change_to_rust {
-            let html_string: String = html_buffer.iter().collect();
-            html_parts.push(html_string);
-            html_buffer.clear();
+    quote! {
+        rust_html::Asset {
+            path: #relative_path_literal,
+            url: #url_literal,
+            integrity: #integrity_literal,
+            // Also recompiles when the file changes
+            contents: include_bytes!(#path_literal),
         }
+    }
+}
+
+/// Renders a placeholder with the escaping of its position, making
+/// sure values in URL attributes are safe URLs
+fn render_placeholder(evaluator: &TokenStream, placeholder: Placeholder) -> TokenStream {
+    match placeholder {
+        Placeholder::Content => quote! { rust_html::Render::render(&#evaluator) },
+        Placeholder::AttributeValue => quote! { rust_html::Render::render_attribute(&#evaluator) },
+        Placeholder::UrlAttribute => quote! {
+            rust_html::Render::render(&rust_html::SafeUrl(rust_html::Render::render_attribute(&#evaluator)))
+        },
+    }
+}
 
-        // When exiting rust, verify and add to evaluators
-        if change_to_html {
-            let rust_string: String = rust_buffer.iter().collect();
-            let rust_evaluator = match inner_rust_to_tokens(&rust_string) {
-                Ok(rust_evaluator) => {
-                    // Validate rust syntax is expr/ident/literal
-                    let html_context: String = html_buffer.iter().collect();
-                    let valid_rust =
-                        validate_inner_rust(&rust_evaluator, &rust_string, &html_context);
-                    match valid_rust {
-                        Ok(_) => rust_evaluator,
-                        Err(inner_err) => return Err(inner_err),
-                    }
-                }
-                Err(err) => return Err(err),
-            };
-            rust_evaluators.push(rust_evaluator);
-            rust_buffer.clear();
+/// Converts a rust evaluator to a future rendering the placeholder with
+/// the escaping of its position. Awaited expressions are rendered with
+/// `AsyncRender`.
+fn render_future(evaluator: &TokenStream, placeholder: Placeholder) -> TokenStream {
+    let Ok(syn::Expr::Await(awaited)) = syn::parse2::<syn::Expr>(evaluator.clone()) else {
+        let rendered = render_placeholder(evaluator, placeholder);
+        return quote! { ::std::future::ready(#rendered) };
+    };
+    let base = awaited.base;
+    match placeholder {
+        Placeholder::Content => quote! { rust_html::AsyncRender::render_async(#base) },
+        Placeholder::AttributeValue => {
+            quote! { rust_html::AsyncRender::render_attribute_async(#base) }
         }
+        Placeholder::UrlAttribute => quote! {
+            async move {
+                let value = rust_html::AsyncRender::render_attribute_async(#base).await;
+                rust_html::Render::render(&rust_html::SafeUrl(value))
+            }
+        },
     }
+}
+
+/// Markup language of a template, used for validation
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Html,
+    Xml,
+}
 
-    if depth > 0 {
-        return Err(compile_error("Missing close bracket '}'"));
+/// Validated template ready for code generation
+struct PreparedTemplate {
+    html_literals: Vec<TokenStream>,
+    template_end_literal: TokenStream,
+    rust_evaluators: Vec<TokenStream>,
+    rust_sources: Vec<String>,
+    /// Position of each placeholder
+    placeholders: Vec<Placeholder>,
+    /// Lints reported as warnings
+    warnings: Vec<TokenStream>,
+    /// Recompiles the template when the crate config changes
+    config_tracking: TokenStream,
+}
+
+/// Parses and validates macro input (options and a string literal)
+fn prepare_template(input: TokenStream) -> Result<PreparedTemplate, TokenStream> {
+    // Parse input string inside macro
+    let config = config::load()?;
+    let (options, input_string) = parse_input(input, config.options)?;
+    let mut prepared = prepare_template_string(&input_string, Syntax::Html, &options)?;
+    prepared.config_tracking = track_file(config.path.as_deref());
+    Ok(prepared)
+}
+
+/// Recompiles the template when the file changes
+fn track_file(path: Option<&std::path::Path>) -> TokenStream {
+    let Some(path) = path else {
+        return TokenStream::new();
+    };
+    let path_literal = string_to_literal(&path.to_string_lossy());
+    quote! { const _: &str = include_str!(#path_literal); }
+}
+
+/// Parses and validates template content
+fn prepare_template_string(
+    input_string: &str,
+    syntax: Syntax,
+    options: &TemplateOptions,
+) -> Result<PreparedTemplate, TokenStream> {
+    // Convert contents to html template and list of rust evaluators
+    let ParsedRhtml {
+        html_parts,
+        mut rust_evaluators,
+        mut rust_sources,
+    } = parse_rhtml(input_string)?;
+
+    // Compile time syntax check
+    let (html_parts, placeholders, warnings) = match syntax {
+        Syntax::Html => {
+            let PreparedHtml {
+                mut html_parts,
+                mut placeholders,
+                scoped_css,
+                warnings,
+            } = prepare_html(html_parts, options).map_err(|error| compile_error(&error))?;
+            let warnings = warnings
+                .iter()
+                .map(|lint| compile_warning(lint.name, &lint.message))
+                .collect();
+            // Move `<style scoped>` CSS to a placeholder, see `Template::scoped_css`
+            if let Some(css) = scoped_css {
+                let css = string_to_literal(&css);
+                html_parts.insert(0, String::new());
+                rust_evaluators.insert(
+                    0,
+                    quote! { rust_html::Template::scoped_style_internal(#css) },
+                );
+                rust_sources.insert(0, String::new());
+                placeholders.insert(0, Placeholder::Content);
+            }
+            (html_parts, placeholders, warnings)
+        }
+        Syntax::Xml => {
+            let mut html_parts = html_parts;
+            let html_for_validate = trim_whitespace_per_line(&html_parts.join(""));
+            compile_check_xml(&html_for_validate)?;
+            // The XML declaration must be at the very start of the document
+            if html_for_validate.starts_with("<?xml") {
+                html_parts[0] = html_parts[0].trim_start().to_string();
+            }
+            let placeholders = vec![Placeholder::Content; rust_evaluators.len()];
+            (html_parts, placeholders, vec![])
+        }
+    };
+
+    // Build literals
+    let mut html_literals: Vec<_> = html_parts
+        .iter()
+        .map(|part| string_to_literal(part))
+        .collect();
+    let Some(template_end_literal) = html_literals.pop() else {
+        return Err(compile_error("internal"));
+    };
+
+    if html_literals.len() != rust_evaluators.len() {
+        return Err(compile_error(
+            "unexpected number of parameters, this might be an internal rust_html error",
+        ));
     }
 
-    let last_part = html_buffer.iter().collect();
-    html_parts.push(last_part);
+    Ok(PreparedTemplate {
+        html_literals,
+        template_end_literal,
+        rust_evaluators,
+        rust_sources,
+        placeholders,
+        warnings,
+        config_tracking: TokenStream::new(),
+    })
+}
 
-    Ok((html_parts, rust_evaluators))
+/// HTML string template, list of rust token streams
+/// to inject into the string and their source code
+struct ParsedRhtml {
+    html_parts: Vec<String>,
+    rust_evaluators: Vec<TokenStream>,
+    rust_sources: Vec<String>,
+}
+
+/// Parses rhtml content into HTML parts and rust evaluators
+fn parse_rhtml(input: &str) -> Result<ParsedRhtml, TokenStream> {
+    let (html_parts, rust_sources) =
+        split_placeholders(input).map_err(|error| compile_error(&error))?;
+    let mut rust_evaluators = vec![];
+    for rust_string in &rust_sources {
+        // Validate rust syntax is expr/ident/literal
+        let rust_evaluator = inner_rust_to_tokens(rust_string)?;
+        validate_inner_rust(&rust_evaluator, rust_string, "")?;
+        rust_evaluators.push(rust_evaluator);
+    }
+    Ok(ParsedRhtml {
+        html_parts,
+        rust_evaluators,
+        rust_sources,
+    })
 }
@@ -0,0 +1,121 @@
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use rust_html_parser::{Level, TemplateOptions};
+
+use crate::util::{compile_error, validate_input};
+
+/// Splits macro input into the leading options and the template literal.
+/// Options are added to the `defaults` from the crate config.
+pub fn parse_input(
+    stream: TokenStream,
+    defaults: TemplateOptions,
+) -> Result<(TemplateOptions, String), TokenStream> {
+    let mut segments: Vec<Vec<TokenTree>> = vec![vec![]];
+    for token in stream {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => segments.push(vec![]),
+            _ => segments.last_mut().unwrap().push(token),
+        }
+    }
+    // Allow a trailing comma after the template
+    if segments.len() > 1 && segments.last().is_some_and(|segment| segment.is_empty()) {
+        segments.pop();
+    }
+
+    let template = segments.pop().unwrap_or_default();
+    let mut options = defaults;
+    for option in segments {
+        parse_option(option, &mut options)?;
+    }
+    let template = validate_input(template.into_iter().collect())?;
+    Ok((options, template))
+}
+
+fn parse_option(tokens: Vec<TokenTree>, options: &mut TemplateOptions) -> Result<(), TokenStream> {
+    match tokens.as_slice() {
+        [TokenTree::Ident(ident), value] if ident == "in" => {
+            if options.context.is_some() {
+                return Err(compile_error(
+                    "the template context is given more than once",
+                ));
+            }
+            let context = validate_input(value.clone().into())?;
+            let valid_name = context.starts_with(|c: char| c.is_ascii_alphabetic())
+                && context
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-');
+            if !valid_name {
+                let error = format!("invalid context element name '{}'", context);
+                return Err(compile_error(&error));
+            }
+            options.context = Some(context.to_ascii_lowercase());
+            Ok(())
+        }
+        [TokenTree::Ident(ident), TokenTree::Group(group)]
+            if ident == "prefixes" && group.delimiter() == Delimiter::Parenthesis =>
+        {
+            for token in group.stream() {
+                match token {
+                    TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+                    token => {
+                        let prefix = validate_input(token.into())?;
+                        options.prefixes.push(prefix.to_ascii_lowercase());
+                    }
+                }
+            }
+            Ok(())
+        }
+        [TokenTree::Ident(ident), TokenTree::Group(group)]
+            if ident == "minify" && group.delimiter() == Delimiter::Parenthesis =>
+        {
+            options.minify = match group.stream().to_string().as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                other => {
+                    let error = format!(
+                        "expected `minify(true)` or `minify(false)`, found '{}'",
+                        other
+                    );
+                    return Err(compile_error(&error));
+                }
+            };
+            Ok(())
+        }
+        [TokenTree::Ident(ident), TokenTree::Group(group)]
+            if group.delimiter() == Delimiter::Parenthesis =>
+        {
+            let level = match ident.to_string().as_str() {
+                "allow" => Level::Allow,
+                "warn" => Level::Warn,
+                "deny" => Level::Deny,
+                other => {
+                    let error = format!(
+                        "unknown template option '{}(...)', expected allow, warn, deny, prefixes or minify",
+                        other
+                    );
+                    return Err(compile_error(&error));
+                }
+            };
+            for token in group.stream() {
+                match token {
+                    TokenTree::Ident(lint) => options
+                        .set_level(&lint.to_string(), level)
+                        .map_err(|error| compile_error(&error))?,
+                    TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+                    other => {
+                        let error = format!("expected a lint name, but found '{}'", other);
+                        return Err(compile_error(&error));
+                    }
+                }
+            }
+            Ok(())
+        }
+        _ => {
+            let option: TokenStream = tokens.into_iter().collect();
+            let error = format!(
+                "unknown template option '{}', expected e.g. `in \"ul\"` or `allow(fixups)`",
+                option
+            );
+            Err(compile_error(&error))
+        }
+    }
+}
@@ -130,22 +130,27 @@ pub fn compile_error(error: &str) -> TokenStream {
     .collect()
 }
 
-/// Removes empty lines and whitespace surroinding each line
-/// Necessary for certain quirks of the html5ever parser
+/// Utility for returning a compile warning stream. Stable Rust has no API
+/// for warnings from proc macros, so this uses a deprecated constant
+/// named after the lint, e.g. "use of deprecated constant `img_alt`".
 ///
-/// E.g <tr> rows must be trimmed this way
-/// since whitespace is parsed as text tokens.
-pub fn trim_whitespace_per_line(html: &str) -> String {
-    html.split('\n')
-        .filter_map(|line| {
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed)
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
-        .to_string()
+/// With the `nightly` feature, the warning is emitted as a diagnostic
+/// instead, and the returned stream is empty.
+pub fn compile_warning(lint: &str, message: &str) -> TokenStream {
+    #[cfg(feature = "nightly")]
+    if proc_macro::is_available() {
+        proc_macro::Span::call_site()
+            .warning(format!("{} ({})", message, lint))
+            .emit();
+        return TokenStream::new();
+    }
+    let name = proc_macro2::Ident::new(lint, Span::call_site());
+    quote::quote! {
+        {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const #name: () = ();
+            let _ = #name;
+        }
+    }
 }
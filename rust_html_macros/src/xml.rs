@@ -0,0 +1,216 @@
+use proc_macro2::TokenStream;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+/// Entities predefined by XML, any other named entity is an error
+const XML_ENTITIES: [&str; 5] = ["amp", "lt", "gt", "quot", "apos"];
+
+pub fn compile_check_xml(xml: &str) -> Result<(), TokenStream> {
+    let Err(errors) = validate_xml(xml) else {
+        return Ok(());
+    };
+    // Convert to stream
+    let error = crate::util::compile_error(&format!(
+        "invalid XML syntax ({} issues):\n{}",
+        errors.len(),
+        errors.join("\n")
+    ));
+    Err(error)
+}
+
+/// Checks that the XML is well-formed. Templates starting with an XML
+/// declaration or DOCTYPE are checked as documents, others as fragments.
+fn validate_xml(xml: &str) -> Result<(), Vec<String>> {
+    let is_document = xml.starts_with("<?xml") || xml.starts_with("<!DOCTYPE");
+    let tokenizer = if is_document {
+        Tokenizer::from(xml)
+    } else {
+        Tokenizer::from_fragment(xml, 0..xml.len())
+    };
+
+    let mut errors = vec![];
+    let mut open_elements: Vec<String> = vec![];
+    let mut attributes: Vec<String> = vec![];
+    let mut root_elements = 0;
+
+    for token in tokenizer {
+        let token = match token {
+            Ok(token) => token,
+            Err(err) => {
+                errors.push(err.to_string());
+                break;
+            }
+        };
+        match token {
+            Token::ElementStart { prefix, local, .. } => {
+                if open_elements.is_empty() {
+                    root_elements += 1;
+                }
+                open_elements.push(qualified_name(prefix.as_str(), local.as_str()));
+                attributes.clear();
+            }
+            Token::Attribute {
+                prefix,
+                local,
+                value,
+                ..
+            } => {
+                let name = qualified_name(prefix.as_str(), local.as_str());
+                if attributes.contains(&name) {
+                    errors.push(format!("duplicate attribute '{}'", name));
+                }
+                check_entities(value.as_str(), &mut errors);
+                attributes.push(name);
+            }
+            Token::ElementEnd { end, .. } => match end {
+                ElementEnd::Open => {}
+                ElementEnd::Empty => {
+                    open_elements.pop();
+                }
+                ElementEnd::Close(prefix, local) => {
+                    let name = qualified_name(prefix.as_str(), local.as_str());
+                    match open_elements.pop() {
+                        Some(open) if open == name => {}
+                        Some(open) => errors.push(format!(
+                            "closing tag '</{}>' does not match open element '<{}>'",
+                            name, open
+                        )),
+                        None => errors.push(format!("unexpected closing tag '</{}>'", name)),
+                    }
+                }
+            },
+            Token::Text { text } => check_entities(text.as_str(), &mut errors),
+            _ => {}
+        }
+    }
+
+    for open in open_elements.iter().rev() {
+        errors.push(format!("element '<{}>' is never closed", open));
+    }
+    if is_document && root_elements != 1 {
+        errors.push(format!(
+            "XML documents must have exactly one root element, found {}",
+            root_elements
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn qualified_name(prefix: &str, local: &str) -> String {
+    if prefix.is_empty() {
+        local.to_string()
+    } else {
+        format!("{}:{}", prefix, local)
+    }
+}
+
+/// Verifies that all entity references are predefined XML
+/// entities or character references, e.g. `&amp;` or `&#160;`
+fn check_entities(text: &str, errors: &mut Vec<String>) {
+    for (index, _) in text.match_indices('&') {
+        let rest = &text[index + 1..];
+        let Some(end) = rest.find(';') else {
+            errors.push(format!("unescaped '&' in '{}', use '&amp;'", text));
+            continue;
+        };
+        let entity = &rest[..end];
+        let valid = if let Some(hex) = entity.strip_prefix("#x") {
+            !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+        } else if let Some(decimal) = entity.strip_prefix('#') {
+            !decimal.is_empty() && decimal.chars().all(|c| c.is_ascii_digit())
+        } else {
+            XML_ENTITIES.contains(&entity)
+        };
+        if !valid {
+            errors.push(format!(
+                "unknown entity '&{};', XML only defines &amp; &lt; &gt; &quot; &apos;",
+                entity
+            ));
+        }
+    }
+}
+
+/// Unit tests for XML validation
+#[cfg(test)]
+mod test_xml_validation {
+    use crate::xml::validate_xml;
+
+    #[test]
+    fn test_document() {
+        valid(
+            r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <rss version="2.0">
+                <channel><title>Blog</title></channel>
+            </rss>
+        "#,
+        );
+        invalid(r#"<?xml version="1.0"?>"#);
+        invalid(r#"<?xml version="1.0"?><a></a><b></b>"#);
+        invalid(r#"<a></a><?xml version="1.0"?>"#);
+    }
+
+    #[test]
+    fn test_fragment() {
+        valid("<item><title>Post</title></item>");
+        valid("<item></item><item></item>");
+        valid("just text");
+        valid("");
+        valid("<atom:link href=\"/feed.xml\" rel=\"self\"/>");
+    }
+
+    #[test]
+    fn test_tags() {
+        valid("<br/>");
+        invalid("<br>");
+        invalid("<a><b></a></b>");
+        invalid("</a>");
+        invalid("<A></a>");
+    }
+
+    #[test]
+    fn test_attributes() {
+        valid("<a b=\"1\" c='2'/>");
+        invalid("<a b=1/>");
+        invalid("<a b/>");
+        invalid("<a b=\"1\" b=\"2\"/>");
+        invalid("<a b=\"<\"/>");
+    }
+
+    #[test]
+    fn test_entities() {
+        valid("<a>&amp; &lt; &gt; &quot; &apos; &#160; &#xA0;</a>");
+        valid("<a b=\"&amp;\"/>");
+        invalid("<a>&nbsp;</a>");
+        invalid("<a>fish & chips</a>");
+        invalid("<a b=\"&copy;\"/>");
+    }
+
+    #[test]
+    fn test_cdata_comment() {
+        valid("<a><![CDATA[<b>&nbsp;</b>]]></a>");
+        valid("<!-- comment --><a/>");
+        invalid("<a><!-- -- --></a>");
+    }
+
+    fn valid(xml: &str) {
+        let trimmed = rust_html_parser::trim_whitespace_per_line(xml);
+        let result = validate_xml(&trimmed);
+        assert!(
+            result.is_ok(),
+            "{} is invalid: {}",
+            &trimmed,
+            result.unwrap_err().join(", ")
+        );
+    }
+
+    fn invalid(xml: &str) {
+        let trimmed = rust_html_parser::trim_whitespace_per_line(xml);
+        let result = validate_xml(&trimmed);
+        assert!(result.is_err(), "Expected not valid: {}", xml);
+    }
+}
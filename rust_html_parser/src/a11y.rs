@@ -0,0 +1,262 @@
+use scraper::{node::Element, ElementRef, Node};
+
+use crate::options::{has_dummy_attribute, lint, Lint};
+use crate::placeholders::DUMMY_PREFIX;
+
+/// Non-abstract WAI-ARIA roles
+const ARIA_ROLES: &str = "alert alertdialog application article banner blockquote button \
+    caption cell checkbox code columnheader combobox complementary contentinfo definition \
+    deletion dialog directory document emphasis feed figure form generic grid gridcell group \
+    heading img insertion link list listbox listitem log main marquee math menu menubar \
+    menuitem menuitemcheckbox menuitemradio meter navigation none note option paragraph \
+    presentation progressbar radio radiogroup region row rowgroup rowheader scrollbar search \
+    searchbox separator slider spinbutton status strong subscript superscript switch tab \
+    table tablist tabpanel term textbox time timer toolbar tooltip tree treegrid treeitem";
+
+/// WAI-ARIA states and properties, without the `aria-` prefix
+const ARIA_ATTRIBUTES: &str = "activedescendant atomic autocomplete braillelabel \
+    brailleroledescription busy checked colcount colindex colindextext colspan controls \
+    current describedby description details disabled dropeffect errormessage expanded flowto \
+    grabbed haspopup hidden invalid keyshortcuts label labelledby level live modal multiline \
+    multiselectable orientation owns placeholder posinset pressed readonly relevant required \
+    roledescription rowcount rowindex rowindextext rowspan selected setsize sort valuemax \
+    valuemin valuenow valuetext";
+
+/// Input types that don't need a label
+const UNLABELLED_INPUT_TYPES: [&str; 5] = ["hidden", "submit", "reset", "button", "image"];
+
+/// Basic accessibility checks on a parsed template. Elements with a
+/// placeholder in their attribute list are skipped where a missing
+/// attribute is reported, since it could be rendered at runtime.
+pub fn check_accessibility(html: &scraper::Html, document: bool) -> Vec<Lint> {
+    let mut lints = vec![];
+    let label_targets: Vec<_> = elements(html)
+        .filter(|element| element.value().name() == "label")
+        .filter_map(|element| element.value().attr("for"))
+        .collect();
+    let mut previous_heading = None;
+
+    for node in elements(html) {
+        let element = node.value();
+        let name = element.name();
+        let dynamic = has_dummy_attribute(element);
+
+        if name == "img" && !dynamic && element.attr("alt").is_none() {
+            lints.push(lint(
+                "img_alt",
+                "'<img>' needs an alt attribute, use alt=\"\" for decorative images",
+            ));
+        }
+
+        let is_input = match name {
+            "input" => element
+                .attr("type")
+                .is_none_or(|kind| !UNLABELLED_INPUT_TYPES.contains(&&*kind.to_ascii_lowercase())),
+            "select" | "textarea" => true,
+            _ => false,
+        };
+        let is_labelled = has_label_attribute(element)
+            || element.id().is_some_and(|id| {
+                // Dynamic ids may match any dynamic label target
+                label_targets.iter().any(|target| {
+                    *target == id || (target.contains(DUMMY_PREFIX) && id.contains(DUMMY_PREFIX))
+                })
+            })
+            || node.ancestors().any(|ancestor| {
+                ancestor
+                    .value()
+                    .as_element()
+                    .is_some_and(|element| element.name() == "label")
+            });
+        if is_input && !dynamic && !is_labelled {
+            lints.push(lint(
+                "input_label",
+                &format!(
+                    "'<{}>' needs a label, wrap it in '<label>', use <label for=\"...\"> or aria-label",
+                    name
+                ),
+            ));
+        }
+
+        if name == "button" && !dynamic && !has_label_attribute(element) && !has_text(node) {
+            lints.push(lint(
+                "button_text",
+                "'<button>' needs text content, an image with alt text or aria-label",
+            ));
+        }
+
+        check_aria(element, &mut lints);
+
+        if let Some(level) = heading_level(name) {
+            if let Some(previous) = previous_heading {
+                if level > previous + 1 {
+                    lints.push(lint(
+                        "heading_order",
+                        &format!(
+                            "'<h{}>' follows '<h{}>', heading levels should not be skipped",
+                            level, previous
+                        ),
+                    ));
+                }
+            }
+            previous_heading = Some(level);
+        }
+
+        if document && name == "html" && !dynamic && element.attr("lang").is_none() {
+            lints.push(lint(
+                "html_lang",
+                "'<html>' needs a lang attribute, e.g. lang=\"en\"",
+            ));
+        }
+    }
+    lints
+}
+
+fn check_aria(element: &Element, lints: &mut Vec<Lint>) {
+    if let Some(role) = element
+        .attr("role")
+        .filter(|role| !role.contains(DUMMY_PREFIX))
+    {
+        for role in role.split_whitespace() {
+            if !ARIA_ROLES.split_whitespace().any(|known| known == role) {
+                lints.push(lint("aria", &format!("unknown ARIA role '{}'", role)));
+            }
+        }
+    }
+    for (attribute, _) in element.attrs() {
+        let Some(property) = attribute.strip_prefix("aria-") else {
+            continue;
+        };
+        if !ARIA_ATTRIBUTES
+            .split_whitespace()
+            .any(|known| known == property)
+        {
+            lints.push(lint(
+                "aria",
+                &format!("unknown ARIA attribute '{}'", attribute),
+            ));
+        }
+    }
+}
+
+/// All elements in document order
+fn elements(html: &scraper::Html) -> impl Iterator<Item = ElementRef<'_>> {
+    html.tree.root().descendants().filter_map(ElementRef::wrap)
+}
+
+fn has_label_attribute(element: &Element) -> bool {
+    ["aria-label", "aria-labelledby", "title"]
+        .iter()
+        .any(|name| {
+            element
+                .attr(name)
+                .is_some_and(|value| !value.trim().is_empty())
+        })
+}
+
+/// Whether the element contains text or an image with alt text
+fn has_text(node: ElementRef<'_>) -> bool {
+    node.descendants().any(|child| match child.value() {
+        Node::Text(text) => !text.trim().is_empty(),
+        Node::Element(element) => {
+            element.name() == "img"
+                && element
+                    .attr("alt")
+                    .is_some_and(|alt| !alt.trim().is_empty())
+        }
+        _ => false,
+    })
+}
+
+fn heading_level(name: &str) -> Option<u8> {
+    match name {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+/// Unit tests for accessibility lints
+#[cfg(test)]
+mod test_a11y {
+    use crate::a11y::check_accessibility;
+
+    #[test]
+    fn test_img_alt() {
+        lints("<img src=\"a.png\">", &["img_alt"]);
+        no_lints("<img src=\"a.png\" alt=\"\"><img src=\"b.png\" alt=\"B\">");
+        no_lints("<img src=\"a.png\" data-rhtml-placeholder-0>");
+    }
+
+    #[test]
+    fn test_input_label() {
+        lints(
+            "<input type=\"text\"><select></select><textarea></textarea>",
+            &["input_label", "input_label", "input_label"],
+        );
+        no_lints("<label>Name <input name=\"name\"></label>");
+        no_lints("<label for=\"name\">Name</label><input id=\"name\">");
+        no_lints("<input aria-label=\"Search\"><input type=\"hidden\"><input type=\"submit\">");
+        no_lints(
+            "<label for=\"rhtml-placeholder-0\">Name</label><input id=\"rhtml-placeholder-1\">",
+        );
+    }
+
+    #[test]
+    fn test_button_text() {
+        lints(
+            "<button></button><button> <span></span> </button>",
+            &["button_text", "button_text"],
+        );
+        no_lints("<button>Save</button><button><span>Save</span></button>");
+        no_lints("<button aria-label=\"Close\"></button>");
+        no_lints("<button><img src=\"x.png\" alt=\"Close\"></button>");
+        no_lints("<button>rhtml-placeholder-0</button>");
+    }
+
+    #[test]
+    fn test_aria() {
+        lints("<div role=\"buton\"></div>", &["aria"]);
+        lints("<div aria-lable=\"x\"></div>", &["aria"]);
+        no_lints("<nav role=\"navigation\" aria-label=\"Main\"></nav>");
+        no_lints("<div role=\"switch button\" aria-checked=\"true\"></div>");
+        no_lints("<div role=\"rhtml-placeholder-0\"></div>");
+    }
+
+    #[test]
+    fn test_heading_order() {
+        lints("<h1>a</h1><h3>b</h3>", &["heading_order"]);
+        no_lints("<h2>a</h2><h3>b</h3><h2>c</h2><h1>d</h1><h2>e</h2>");
+        no_lints("<h3>Component heading</h3>");
+    }
+
+    #[test]
+    fn test_html_lang() {
+        let html = scraper::Html::parse_document("<!DOCTYPE html><html><body></body></html>");
+        assert_eq!(names(&html, true), ["html_lang"]);
+        let html = scraper::Html::parse_document("<!DOCTYPE html><title>Page</title>");
+        assert_eq!(names(&html, true), ["html_lang"]);
+        let html = scraper::Html::parse_document("<!DOCTYPE html><html lang=\"en\"></html>");
+        assert!(names(&html, true).is_empty());
+    }
+
+    fn names(html: &scraper::Html, document: bool) -> Vec<&'static str> {
+        check_accessibility(html, document)
+            .into_iter()
+            .map(|lint| lint.name)
+            .collect()
+    }
+
+    fn lints(html: &str, expected: &[&str]) {
+        let parsed = scraper::Html::parse_fragment(html);
+        assert_eq!(names(&parsed, false), expected, "{}", html);
+    }
+
+    fn no_lints(html: &str) {
+        lints(html, &[]);
+    }
+}
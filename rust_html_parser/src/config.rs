@@ -0,0 +1,175 @@
+use std::path::{Path, PathBuf};
+
+use crate::options::{Level, TemplateOptions};
+
+/// Config file next to `Cargo.toml`, used instead of `[package.metadata.rust_html]`
+const CONFIG_FILE: &str = "rust_html.toml";
+
+/// Crate-wide defaults for template options, e.g.
+///
+/// ```toml
+/// [package.metadata.rust_html]
+/// minify = true
+/// prefixes = ["hx-", "x-", "@"]
+///
+/// [package.metadata.rust_html.lints]
+/// a11y = "deny"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub options: TemplateOptions,
+    /// File the config was read from, to recompile when it changes.
+    /// `Cargo.toml` when there is no config yet, since adding one changes it.
+    pub path: Option<PathBuf>,
+}
+
+/// Reads the config of the crate in `manifest_dir`
+pub fn read_config(manifest_dir: &Path) -> Result<Config, String> {
+    let path = manifest_dir.join(CONFIG_FILE);
+    if path.exists() {
+        let table = read_toml(&path)?;
+        let options = parse_config(&table).map_err(|error| in_file(&path, &error))?;
+        return Ok(Config {
+            options,
+            path: Some(path),
+        });
+    }
+
+    let path = manifest_dir.join("Cargo.toml");
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let manifest = read_toml(&path)?;
+    let section = manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("rust_html"));
+    let Some(section) = section else {
+        return Ok(Config {
+            options: TemplateOptions::default(),
+            path: Some(path),
+        });
+    };
+    let options = section
+        .as_table()
+        .ok_or("expected [package.metadata.rust_html] to be a table".to_string())
+        .and_then(parse_config)
+        .map_err(|error| in_file(&path, &error))?;
+    Ok(Config {
+        options,
+        path: Some(path),
+    })
+}
+
+fn read_toml(path: &Path) -> Result<toml::Table, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read '{}': {}", path.display(), err))?;
+    content
+        .parse()
+        .map_err(|err| format!("failed to parse '{}': {}", path.display(), err))
+}
+
+fn in_file(path: &Path, error: &str) -> String {
+    format!(
+        "invalid rust_html config in '{}': {}",
+        path.display(),
+        error
+    )
+}
+
+/// Converts a config table to the default template options
+pub fn parse_config(table: &toml::Table) -> Result<TemplateOptions, String> {
+    let mut options = TemplateOptions::default();
+    for (key, value) in table {
+        match key.as_str() {
+            "minify" => {
+                let minify = value
+                    .as_bool()
+                    .ok_or("expected `minify` to be true or false")?;
+                options.minify = Some(minify);
+            }
+            "prefixes" => {
+                let prefixes = value
+                    .as_array()
+                    .ok_or("expected `prefixes` to be a list of strings")?;
+                for prefix in prefixes {
+                    let prefix = prefix
+                        .as_str()
+                        .ok_or("expected `prefixes` to be a list of strings")?;
+                    options.prefixes.push(prefix.to_ascii_lowercase());
+                }
+            }
+            "lints" => {
+                let lints = value
+                    .as_table()
+                    .ok_or("expected `lints` to be a table of lint levels")?;
+                for (lint, level) in lints {
+                    let level = match level.as_str() {
+                        Some("allow") => Level::Allow,
+                        Some("warn") => Level::Warn,
+                        Some("deny") => Level::Deny,
+                        _ => {
+                            return Err(format!(
+                                "expected the level of '{}' to be \"allow\", \"warn\" or \"deny\"",
+                                lint
+                            ))
+                        }
+                    };
+                    options.set_level(lint, level)?;
+                }
+            }
+            other => {
+                return Err(format!(
+                    "unknown key '{}', expected minify, prefixes or lints",
+                    other
+                ))
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Unit tests for the crate-wide config
+#[cfg(test)]
+mod test_config {
+    use crate::config::parse_config;
+    use crate::options::Level;
+
+    #[test]
+    fn test_parse_config() {
+        let options = parse(
+            r#"
+            minify = true
+            prefixes = ["hx-", "X-"]
+
+            [lints]
+            a11y = "deny"
+            unknown_names = "warn"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(options.minify, Some(true));
+        assert_eq!(options.prefixes, ["hx-", "x-"]);
+        assert_eq!(options.level("img_alt"), Level::Deny);
+        assert_eq!(options.level("unknown_names"), Level::Warn);
+        assert_eq!(options.level("fixups"), Level::Deny);
+        assert_eq!(parse("").unwrap().minify, None);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(parse("minify = \"yes\"").unwrap_err().contains("minify"));
+        assert!(parse("prefixes = [1]").unwrap_err().contains("prefixes"));
+        assert!(parse("[lints]\nimg_alt = \"error\"")
+            .unwrap_err()
+            .contains("'img_alt'"));
+        assert!(parse("[lints]\nimg_alts = \"deny\"")
+            .unwrap_err()
+            .contains("unknown lint 'img_alts'"));
+        assert!(parse("strict = true").unwrap_err().contains("unknown key"));
+    }
+
+    fn parse(config: &str) -> Result<crate::options::TemplateOptions, String> {
+        parse_config(&config.parse().unwrap())
+    }
+}
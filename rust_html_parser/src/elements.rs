@@ -0,0 +1,16 @@
+/// Elements allowed in a standalone SVG fragment
+pub const SVG_ELEMENTS: &str =
+    "a animate animateMotion animateTransform circle clipPath defs desc \
+    ellipse feBlend feColorMatrix feComponentTransfer feComposite feConvolveMatrix \
+    feDiffuseLighting feDisplacementMap feDistantLight feDropShadow feFlood feFuncA feFuncB \
+    feFuncG feFuncR feGaussianBlur feImage feMerge feMergeNode feMorphology feOffset \
+    fePointLight feSpecularLighting feSpotLight feTile feTurbulence filter foreignObject g \
+    image line linearGradient marker mask metadata mpath path pattern polygon polyline \
+    radialGradient rect script set stop style svg switch symbol text textPath title tspan \
+    use view";
+
+/// Elements allowed in a standalone MathML fragment
+pub const MATHML_ELEMENTS: &str = "annotation annotation-xml maction math menclose merror \
+    mfenced mfrac mi mmultiscripts mn mo mover mpadded mphantom mprescripts mroot mrow ms \
+    mspace msqrt mstyle msub msubsup msup mtable mtd mtext mtr munder munderover none \
+    semantics";
@@ -0,0 +1,273 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use html5ever::tokenizer::{
+    states::RawKind, BufferQueue, TagKind, TagToken, Token, TokenSink, TokenSinkResult, Tokenizer,
+    TokenizerOpts,
+};
+use tendril::StrTendril;
+
+/// Elements without an end tag
+pub const VOID_ELEMENTS: &str = "area base br col embed hr img input keygen link meta param \
+    source track wbr";
+
+/// Elements that are optional in a document, the
+/// parser inserts and closes them where needed
+const DOCUMENT_ELEMENTS: [&str; 3] = ["html", "head", "body"];
+
+/// Structural change the HTML parser makes to a template,
+/// so that it renders differently than written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fixup {
+    /// Element without a tag in the template, e.g. `<tbody>`
+    Inserted(String),
+    /// Open element closed by the start of another element,
+    /// e.g. a `<p>` closed by `<div>`
+    Closed { element: String, closed: String },
+    /// Element placed somewhere else in the tree, e.g. by
+    /// foster parenting content out of a `<table>`
+    Moved {
+        element: String,
+        written: Vec<String>,
+        parsed: Vec<String>,
+    },
+}
+
+impl fmt::Display for Fixup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fixup::Inserted(element) => write!(
+                f,
+                "'<{}>' is inserted implicitly by the parser, add it to the template",
+                element
+            ),
+            Fixup::Closed { element, closed } => write!(
+                f,
+                "'<{}>' implicitly closes the open '<{}>', it can't be nested as written",
+                element, closed
+            ),
+            Fixup::Moved {
+                element,
+                written,
+                parsed,
+            } => write!(
+                f,
+                "'<{}>' is moved by the parser from '{}' to '{}'",
+                element,
+                display_path(written),
+                display_path(parsed)
+            ),
+        }
+    }
+}
+
+fn display_path(path: &[String]) -> String {
+    if path.is_empty() {
+        "the template root".to_string()
+    } else {
+        path.join(" > ")
+    }
+}
+
+/// Compares the parsed tree with the tags written in the template.
+/// For documents, `<html>`, `<head>` and `<body>` may be left out.
+pub fn find_fixups(html: &str, parsed: &scraper::Html, document: bool) -> Vec<Fixup> {
+    let ignored = |name: &str| document && DOCUMENT_ELEMENTS.contains(&name);
+
+    // Elements of the parsed tree in document order
+    let fragment_root = parsed.root_element().id();
+    let elements: Vec<_> = parsed
+        .tree
+        .root()
+        .descendants()
+        .filter(|node| document || node.id() != fragment_root)
+        .filter_map(|node| {
+            let name = node.value().as_element()?.name().to_ascii_lowercase();
+            (!ignored(&name)).then_some((node, name))
+        })
+        .collect();
+
+    let mut fixups = vec![];
+    let mut inserted = vec![];
+    let mut next_element = 0;
+    let mut open_elements: Vec<String> = vec![];
+    for (kind, name, self_closing) in tags(html) {
+        if ignored(&name) {
+            continue;
+        }
+        if kind == TagKind::EndTag {
+            if let Some(index) = open_elements.iter().rposition(|open| *open == name) {
+                open_elements.truncate(index);
+            }
+            continue;
+        }
+
+        // Elements before the next written one were inserted by the parser
+        let Some(offset) = elements[next_element..]
+            .iter()
+            .position(|(_, element)| *element == name)
+        else {
+            // Dropped tags are reported as parse errors
+            continue;
+        };
+        for (node, element) in &elements[next_element..next_element + offset] {
+            fixups.push(Fixup::Inserted(element.clone()));
+            inserted.push(node.id());
+        }
+        let (node, _) = &elements[next_element + offset];
+        next_element += offset + 1;
+
+        let mut parsed_path: Vec<String> = node
+            .ancestors()
+            .filter(|ancestor| document || ancestor.id() != fragment_root)
+            .filter(|ancestor| !inserted.contains(&ancestor.id()))
+            .filter_map(|ancestor| Some(ancestor.value().as_element()?.name().to_ascii_lowercase()))
+            .filter(|ancestor| !ignored(ancestor))
+            .collect();
+        parsed_path.reverse();
+        if parsed_path != open_elements {
+            if open_elements.starts_with(&parsed_path) {
+                fixups.push(Fixup::Closed {
+                    element: name.clone(),
+                    closed: open_elements[parsed_path.len()].clone(),
+                });
+            } else {
+                fixups.push(Fixup::Moved {
+                    element: name.clone(),
+                    written: open_elements.clone(),
+                    parsed: parsed_path.clone(),
+                });
+            }
+            // Continue from the structure the parser built
+            open_elements = parsed_path;
+        }
+
+        if !self_closing && !VOID_ELEMENTS.split_whitespace().any(|void| void == name) {
+            open_elements.push(name);
+        }
+    }
+    for (_, element) in &elements[next_element..] {
+        fixups.push(Fixup::Inserted(element.clone()));
+    }
+    fixups
+}
+
+/// Start and end tags in the HTML: kind, lowercase name and self-closing flag
+fn tags(html: &str) -> Vec<(TagKind, String, bool)> {
+    let input = BufferQueue::default();
+    input.push_back(StrTendril::from(html));
+    let tokenizer = Tokenizer::new(TagSink::default(), TokenizerOpts::default());
+    let _ = tokenizer.feed(&input);
+    tokenizer.end();
+    tokenizer.sink.tags.into_inner()
+}
+
+/// Collects tags, switching to raw text where
+/// the tree builder would, e.g. inside `<script>`
+#[derive(Default)]
+struct TagSink {
+    tags: RefCell<Vec<(TagKind, String, bool)>>,
+}
+
+impl TokenSink for TagSink {
+    type Handle = ();
+
+    fn process_token(&self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        let TagToken(tag) = token else {
+            return TokenSinkResult::Continue;
+        };
+        let name = tag.name.to_string();
+        let result = match (tag.kind, name.as_str()) {
+            (TagKind::EndTag, _) => TokenSinkResult::Continue,
+            (_, "script") => TokenSinkResult::RawData(RawKind::ScriptData),
+            (_, "style" | "xmp" | "iframe" | "noembed" | "noframes" | "noscript") => {
+                TokenSinkResult::RawData(RawKind::Rawtext)
+            }
+            (_, "textarea" | "title") => TokenSinkResult::RawData(RawKind::Rcdata),
+            (_, "plaintext") => TokenSinkResult::Plaintext,
+            _ => TokenSinkResult::Continue,
+        };
+        self.tags
+            .borrow_mut()
+            .push((tag.kind, name, tag.self_closing));
+        result
+    }
+}
+
+/// Unit tests for parser fix-up detection
+#[cfg(test)]
+mod test_fixups {
+    use crate::options::{Level, TemplateOptions};
+    use crate::parse::check_html;
+
+    #[test]
+    fn test_nested_as_written() {
+        no_fixups("<div><p>text</p><ul><li>a</li><li>b</li></ul></div>");
+        no_fixups("<p>a<br>b<img src=\"x.png\" alt=\"x\"></p>");
+        no_fixups("<table><tbody><tr><td>cell</td></tr></tbody></table>");
+        no_fixups("<svg><path d=\"M0 0\"/><g><circle r=\"1\"/></g></svg>");
+        no_fixups("<script>if (a<b && c<d) {}</script><div></div>");
+        no_fixups("<textarea><p>text</textarea>");
+        no_fixups("<tr><td>cell</td></tr>");
+        no_fixups("<td>cell</td>");
+        no_fixups(include_str!("./test.html"));
+    }
+
+    #[test]
+    fn test_document() {
+        no_fixups("<!DOCTYPE html><title>Page</title><p>text</p>");
+        no_fixups("<!DOCTYPE html><html><head><title>Page</title><body><p>text</p></html>");
+        no_fixups("<head></head><body></body>");
+    }
+
+    #[test]
+    fn test_implicit_closing() {
+        fixups(
+            "<p>text<div>block</div>",
+            "implicitly closes the open '<p>'",
+        );
+        fixups("<ul><li>a<li>b</ul>", "implicitly closes the open '<li>'");
+        fixups("<p>a<p>b", "implicitly closes the open '<p>'");
+        fixups("<select><option>a<option>b</select>", "'<option>'");
+    }
+
+    #[test]
+    fn test_inserted() {
+        fixups(
+            "<table><tr><td>cell</td></tr></table>",
+            "'<tbody>' is inserted implicitly",
+        );
+        fixups(
+            "<table><col></table>",
+            "'<colgroup>' is inserted implicitly",
+        );
+    }
+
+    #[test]
+    fn test_allow_fixups() {
+        let options = TemplateOptions {
+            levels: vec![("fixups".to_string(), Level::Allow)],
+            ..Default::default()
+        };
+        let html = "<table><tr><td>cell</td></tr></table>";
+        assert!(check_html(html, &options).is_ok());
+    }
+
+    fn no_fixups(html: &str) {
+        let trimmed = crate::template::trim_whitespace_per_line(html);
+        let result = check_html(&trimmed, &TemplateOptions::default());
+        assert!(
+            result.is_ok(),
+            "{} is restructured: {}",
+            trimmed,
+            result.unwrap_err()
+        );
+    }
+
+    fn fixups(html: &str, expected: &str) {
+        let Err(error) = check_html(html, &TemplateOptions::default()) else {
+            panic!("Expected fix-ups: {}", html);
+        };
+        assert!(error.contains(expected), "{}: {}", html, error);
+    }
+}
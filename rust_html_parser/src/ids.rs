@@ -0,0 +1,229 @@
+use scraper::{node::Element, ElementRef, Node};
+
+use crate::options::{has_dummy_attribute, lint, Lint};
+use crate::placeholders::{DUMMY_PREFIX, RAW_TEXT_ELEMENTS};
+
+/// Reports ids used more than once, and `for`, `aria-labelledby` and
+/// `href="#..."` references to ids that are not in the template.
+/// References are only checked when no placeholder could render an
+/// element, since the id could be part of a child template.
+pub fn check_ids(html: &scraper::Html) -> Vec<Lint> {
+    let elements = elements(html);
+    let (ids, duplicates) = duplicate_ids(&elements);
+    let mut lints: Vec<_> = duplicates
+        .into_iter()
+        .map(|message| lint("duplicate_ids", &message))
+        .collect();
+    if !renders_elements(html) {
+        lints.extend(
+            dangling_references(&elements, &ids, "template")
+                .into_iter()
+                .map(|message| lint("dangling_ids", &message)),
+        );
+    }
+    lints
+}
+
+/// Describes the same issues in a rendered page, see `Template::check_ids`
+/// in rust_html. All placeholders are rendered, so every reference is checked.
+pub fn page_id_issues(html: &str) -> Vec<String> {
+    let start = html.trim_start().get(..5).unwrap_or_default();
+    let html = if start.eq_ignore_ascii_case("<!doc") || start.eq_ignore_ascii_case("<html") {
+        scraper::Html::parse_document(html)
+    } else {
+        scraper::Html::parse_fragment(html)
+    };
+    let elements = elements(&html);
+    let (ids, mut issues) = duplicate_ids(&elements);
+    issues.extend(dangling_references(&elements, &ids, "page"));
+    issues
+}
+
+fn elements(html: &scraper::Html) -> Vec<ElementRef<'_>> {
+    html.tree
+        .root()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .collect()
+}
+
+/// All ids of the elements, and a message for each id used more than once
+fn duplicate_ids<'a>(elements: &[ElementRef<'a>]) -> (Vec<&'a str>, Vec<String>) {
+    let mut ids: Vec<&str> = vec![];
+    let mut messages = vec![];
+    for element in elements {
+        let Some(id) = element.value().id() else {
+            continue;
+        };
+        if ids.contains(&id) && !id.contains(DUMMY_PREFIX) {
+            messages.push(format!("id '{}' is used more than once", id));
+        }
+        ids.push(id);
+    }
+    (ids, messages)
+}
+
+/// A message for each reference to an id that is not in `ids`
+fn dangling_references(elements: &[ElementRef], ids: &[&str], scope: &str) -> Vec<String> {
+    let mut messages = vec![];
+    for element in elements {
+        for (attribute, id) in references(element.value()) {
+            let found = ids
+                .iter()
+                .any(|known| *known == id || matches_dynamic(known, id));
+            if !found && !id.contains(DUMMY_PREFIX) {
+                messages.push(format!(
+                    "{} on '<{}>' refers to id '{}', which is not in the {}",
+                    attribute,
+                    element.value().name(),
+                    id,
+                    scope
+                ));
+            }
+        }
+    }
+    messages
+}
+
+/// Ids referenced by an element, with the attribute that references them
+fn references(element: &Element) -> Vec<(&'static str, &str)> {
+    let mut references = vec![];
+    if let Some(id) = element.attr("for").filter(|_| element.name() == "label") {
+        references.push(("for", id));
+    }
+    if let Some(ids) = element.attr("aria-labelledby") {
+        references.extend(ids.split_whitespace().map(|id| ("aria-labelledby", id)));
+    }
+    if let Some(id) = element
+        .attr("href")
+        .filter(|_| element.name() == "a" || element.name() == "area")
+        .and_then(|href| href.strip_prefix('#'))
+    {
+        // `#` and `#top` link to the top of the page
+        if !id.is_empty() && !id.eq_ignore_ascii_case("top") {
+            references.push(("href", id));
+        }
+    }
+    references
+}
+
+/// Whether a placeholder could render elements, i.e. it is in text
+/// content or the attribute list of an element. The text of raw text
+/// elements like `<script>` is never parsed as markup.
+fn renders_elements(html: &scraper::Html) -> bool {
+    html.tree
+        .root()
+        .descendants()
+        .any(|node| match node.value() {
+            Node::Text(text) => {
+                text.contains(DUMMY_PREFIX)
+                    && node.parent().is_none_or(|parent| {
+                        parent
+                            .value()
+                            .as_element()
+                            .is_none_or(|element| !RAW_TEXT_ELEMENTS.contains(&element.name()))
+                    })
+            }
+            // Stands in for child elements, see `placeholders.rs`
+            Node::Comment(comment) => comment.is_empty(),
+            Node::Element(element) => has_dummy_attribute(element),
+            _ => false,
+        })
+}
+
+/// Whether a reference could match a dynamic id like `item-{index}`
+fn matches_dynamic(id: &str, reference: &str) -> bool {
+    let Some((prefix, rest)) = id.split_once(DUMMY_PREFIX) else {
+        return false;
+    };
+    let suffix = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    if suffix.contains(DUMMY_PREFIX) {
+        return reference.starts_with(prefix);
+    }
+    reference.len() >= prefix.len() + suffix.len()
+        && reference.starts_with(prefix)
+        && reference.ends_with(suffix)
+}
+
+/// Unit tests for id checks
+#[cfg(test)]
+mod test_ids {
+    use crate::ids::{check_ids, page_id_issues};
+
+    #[test]
+    fn test_duplicate_ids() {
+        lints(
+            "<div id=\"a\"></div><p id=\"a\"></p>",
+            &["id 'a' is used more than once"],
+        );
+        no_lints("<div id=\"a\"></div><p id=\"b\"></p>");
+        no_lints("<li id=\"rhtml-placeholder-0\"></li><li id=\"rhtml-placeholder-0\"></li>");
+    }
+
+    #[test]
+    fn test_references() {
+        no_lints("<label for=\"name\">Name</label><input id=\"name\">");
+        no_lints("<h2 id=\"title\">Title</h2><section aria-labelledby=\"title\"></section>");
+        no_lints("<a href=\"#main\">Skip</a><main id=\"main\"></main>");
+        no_lints("<a href=\"#\">Top</a><a href=\"#top\">Top</a><a href=\"/page#id\">Page</a>");
+        lints(
+            "<label for=\"nmae\">Name</label><input id=\"name\">",
+            &["for on '<label>' refers to id 'nmae', which is not in the template"],
+        );
+        lints(
+            "<div aria-labelledby=\"a b\"><span id=\"a\"></span></div>",
+            &["aria-labelledby on '<div>' refers to id 'b', which is not in the template"],
+        );
+        lints(
+            "<a href=\"#footer\">Footer</a>",
+            &["href on '<a>' refers to id 'footer', which is not in the template"],
+        );
+    }
+
+    #[test]
+    fn test_dynamic() {
+        no_lints("<label for=\"rhtml-placeholder-0\">Name</label>");
+        no_lints("<a href=\"#item-3\">Item</a><li id=\"item-rhtml-placeholder-0\"></li>");
+        lints(
+            "<a href=\"#other-3\">Item</a><li id=\"item-rhtml-placeholder-0\"></li>",
+            &["href on '<a>' refers to id 'other-3', which is not in the template"],
+        );
+        // The id could be rendered by a placeholder
+        no_lints("<label for=\"name\">Name</label>rhtml-placeholder-0");
+        no_lints("<label for=\"name\">Name</label><div data-rhtml-placeholder-0></div>");
+        no_lints("<table><tbody><!----></tbody></table><a href=\"#row-1\">Row</a>");
+        lints(
+            "<a href=\"#x\">X</a><script>rhtml-placeholder-0</script>",
+            &["href on '<a>' refers to id 'x', which is not in the template"],
+        );
+    }
+
+    #[test]
+    fn test_page_id_issues() {
+        let page = "<!DOCTYPE html><html><body id=\"top\"><a href=\"#TOP\">Top</a>\
+            <!-- <div id=\"a\"> --><script>'<div id=\"a\">'</script><div id=\"a\"></div>\
+            <a href=\"#a\">A</a></body></html>";
+        assert!(page_id_issues(page).is_empty());
+        assert_eq!(
+            page_id_issues("<p id=\"a\"></p><table><tr><td id=\"a\"></td></tr></table>"),
+            ["id 'a' is used more than once"]
+        );
+        assert_eq!(
+            page_id_issues("<label for=\"x\">X</label>rhtml-placeholder-0"),
+            ["for on '<label>' refers to id 'x', which is not in the page"]
+        );
+    }
+
+    fn lints(html: &str, expected: &[&str]) {
+        let parsed = scraper::Html::parse_fragment(html);
+        let messages: Vec<_> = check_ids(&parsed)
+            .into_iter()
+            .map(|lint| lint.message)
+            .collect();
+        assert_eq!(messages, expected, "{}", html);
+    }
+
+    fn no_lints(html: &str) {
+        lints(html, &[]);
+    }
+}
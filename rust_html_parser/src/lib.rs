@@ -0,0 +1,33 @@
+//! HTML validation shared by the rust_html macros and hot reloading
+//!
+//! Templates are checked at compile time by the `rhtml!` macros, and
+//! again at runtime when `rhtml_file!` templates are hot reloaded.
+//! Both use this crate, so that they accept the same templates.
+//! `Template::check_ids` and htmx out-of-band fragments use it to check
+//! rendered HTML.
+
+#[macro_use]
+extern crate html5ever;
+
+mod a11y;
+mod config;
+mod elements;
+mod fixups;
+mod ids;
+mod minify;
+mod names;
+mod options;
+mod parse;
+mod placeholders;
+mod practices;
+mod scoped;
+mod template;
+
+pub use config::{read_config, Config};
+pub use elements::{MATHML_ELEMENTS, SVG_ELEMENTS};
+pub use ids::page_id_issues;
+pub use options::{Level, Lint, TemplateOptions, LINTS, LINT_GROUPS};
+pub use parse::single_root_attributes;
+pub use template::{
+    prepare_html, split_placeholders, trim_whitespace_per_line, Placeholder, PreparedHtml,
+};
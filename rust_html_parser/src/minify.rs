@@ -0,0 +1,84 @@
+use crate::placeholders::Scanner;
+
+/// Collapses each run of whitespace in text content to a single space,
+/// or a newline if the run contains one. Whitespace in attribute values,
+/// comments, raw text elements like `<script>` and in `<pre>` is kept
+/// as written. The parts are the HTML between placeholders.
+pub fn minify_html_parts(html_parts: &[String]) -> Vec<String> {
+    let mut scanner = Scanner::default();
+    html_parts
+        .iter()
+        .map(|part| minify_part(part, &mut scanner))
+        .collect()
+}
+
+fn minify_part(part: &str, scanner: &mut Scanner) -> String {
+    let mut output = String::with_capacity(part.len());
+    let mut whitespace: Option<char> = None;
+    for (index, c) in part.char_indices() {
+        let collapse = scanner.in_text() && !scanner.inside("pre");
+        scanner.advance(c, &part[index + c.len_utf8()..]);
+        if collapse && c.is_whitespace() {
+            if c == '\n' || whitespace.is_none() {
+                whitespace = Some(if c == '\n' { '\n' } else { ' ' });
+            }
+            continue;
+        }
+        if let Some(whitespace) = whitespace.take() {
+            output.push(whitespace);
+        }
+        output.push(c);
+    }
+    if let Some(whitespace) = whitespace {
+        output.push(whitespace);
+    }
+    output
+}
+
+/// Unit tests for whitespace minification
+#[cfg(test)]
+mod test_minify {
+    use crate::minify::minify_html_parts;
+
+    #[test]
+    fn test_collapse_whitespace() {
+        assert_minified(
+            &["\n    <div>\n        <p>Hello,   world</p>\n    </div>\n"],
+            &["\n<div>\n<p>Hello, world</p>\n</div>\n"],
+        );
+        assert_minified(&["<p>a  <  b</p>"], &["<p>a < b</p>"]);
+        assert_minified(
+            &["<p>\n  Hello ", " \n  and ", "</p>"],
+            &["<p>\nHello ", "\nand ", "</p>"],
+        );
+    }
+
+    #[test]
+    fn test_preserved() {
+        assert_minified(
+            &[r#"<div  title="a   b"  class='c  d'>  x  </div>"#],
+            &[r#"<div  title="a   b"  class='c  d'> x </div>"#],
+        );
+        assert_minified(
+            &["<pre>\n  a\n    b</pre>  <p>  c  </p>"],
+            &["<pre>\n  a\n    b</pre> <p> c </p>"],
+        );
+        assert_minified(
+            &["<script>\n  let a =   ", ";\n</script>\n  <!--  x  -->  "],
+            &["<script>\n  let a =   ", ";\n</script>\n<!--  x  --> "],
+        );
+        assert_minified(
+            &["<textarea name=\"a\">  ", "  </textarea>  "],
+            &["<textarea name=\"a\">  ", "  </textarea> "],
+        );
+        assert_minified(
+            &["<pre><b>  a  </b>\n  b</pre>  <p title=\"a > b\">  c  </p>"],
+            &["<pre><b>  a  </b>\n  b</pre> <p title=\"a > b\"> c </p>"],
+        );
+    }
+
+    fn assert_minified(parts: &[&str], expected: &[&str]) {
+        let parts: Vec<_> = parts.iter().map(|part| part.to_string()).collect();
+        assert_eq!(minify_html_parts(&parts), expected);
+    }
+}
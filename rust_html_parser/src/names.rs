@@ -0,0 +1,254 @@
+use scraper::ElementRef;
+
+use crate::elements::{MATHML_ELEMENTS, SVG_ELEMENTS};
+use crate::options::{is_event_handler, lint, Lint};
+
+/// Elements of the HTML living standard
+const HTML_ELEMENTS: &str = "a abbr address area article aside audio b base bdi bdo blockquote \
+    body br button canvas caption cite code col colgroup data datalist dd del details dfn \
+    dialog div dl dt em embed fieldset figcaption figure footer form h1 h2 h3 h4 h5 h6 head \
+    header hgroup hr html i iframe img input ins kbd label legend li link main map mark menu \
+    meta meter nav noscript object ol optgroup option output p picture pre progress q rp rt \
+    ruby s samp script search section select slot small source span strong sub summary sup \
+    style table tbody td template textarea tfoot th thead time title tr track u ul var video wbr \
+    svg math";
+
+/// Attributes allowed on all HTML elements, event handlers (`on...`),
+/// `data-*` and `aria-*` attributes are allowed too
+const GLOBAL_ATTRIBUTES: &str = "accesskey autocapitalize autocorrect autofocus class \
+    contenteditable dir draggable enterkeyhint hidden id inert inputmode is itemid itemprop \
+    itemref itemscope itemtype lang nonce popover role slot spellcheck style tabindex title \
+    translate writingsuggestions xmlns";
+
+/// Attributes specific to each element
+const ELEMENT_ATTRIBUTES: [(&str, &str); 45] = [
+    (
+        "a",
+        "href target download ping rel hreflang type referrerpolicy",
+    ),
+    (
+        "area",
+        "alt coords shape href target download ping rel referrerpolicy",
+    ),
+    (
+        "audio",
+        "src crossorigin preload autoplay loop muted controls",
+    ),
+    ("base", "href target"),
+    ("blockquote", "cite"),
+    (
+        "button",
+        "command commandfor disabled form formaction formenctype formmethod \
+        formnovalidate formtarget name popovertarget popovertargetaction type value",
+    ),
+    ("canvas", "width height"),
+    ("col", "span"),
+    ("colgroup", "span"),
+    ("data", "value"),
+    ("del", "cite datetime"),
+    ("details", "name open"),
+    ("dialog", "open closedby"),
+    ("embed", "src type width height"),
+    ("fieldset", "disabled form name"),
+    (
+        "form",
+        "accept-charset action autocomplete enctype method name novalidate rel target",
+    ),
+    (
+        "iframe",
+        "src srcdoc name sandbox allow allowfullscreen width height referrerpolicy \
+        loading",
+    ),
+    (
+        "img",
+        "alt src srcset sizes crossorigin usemap ismap width height referrerpolicy \
+        decoding loading fetchpriority",
+    ),
+    (
+        "input",
+        "accept alpha alt autocomplete checked colorspace dirname disabled form \
+        formaction formenctype formmethod formnovalidate formtarget height list max maxlength \
+        min minlength multiple name pattern placeholder popovertarget popovertargetaction \
+        readonly required size src step type value width",
+    ),
+    ("ins", "cite datetime"),
+    ("label", "for"),
+    ("li", "value"),
+    (
+        "link",
+        "href crossorigin rel as media hreflang type sizes imagesrcset imagesizes \
+        referrerpolicy integrity blocking color disabled fetchpriority",
+    ),
+    ("map", "name"),
+    ("meta", "name http-equiv content charset media"),
+    ("meter", "value min max low high optimum"),
+    ("object", "data type name form width height"),
+    ("ol", "reversed start type"),
+    ("optgroup", "disabled label"),
+    ("option", "disabled label selected value"),
+    ("output", "for form name"),
+    ("progress", "value max"),
+    ("q", "cite"),
+    (
+        "script",
+        "src type nomodule async defer crossorigin integrity referrerpolicy blocking \
+        fetchpriority",
+    ),
+    (
+        "select",
+        "autocomplete disabled form multiple name required size",
+    ),
+    ("slot", "name"),
+    ("source", "type media src srcset sizes width height"),
+    ("style", "media blocking"),
+    ("td", "colspan rowspan headers"),
+    (
+        "template",
+        "shadowrootmode shadowrootdelegatesfocus shadowrootclonable \
+        shadowrootserializable",
+    ),
+    (
+        "textarea",
+        "autocomplete cols dirname disabled form maxlength minlength name \
+        placeholder readonly required rows wrap",
+    ),
+    ("th", "colspan rowspan headers scope abbr"),
+    ("time", "datetime"),
+    ("track", "default kind label src srclang"),
+    (
+        "video",
+        "src crossorigin poster preload autoplay playsinline loop muted controls width \
+        height",
+    ),
+];
+
+/// Attribute name prefixes that are always allowed
+const ALLOWED_PREFIXES: [&str; 2] = ["data-", "aria-"];
+
+/// Reports element and attribute names that are not part of the HTML
+/// standard. Custom elements (names with a dash) can have any attribute,
+/// and attributes starting with one of the prefixes are allowed.
+pub fn check_names(html: &scraper::Html, prefixes: &[String]) -> Vec<Lint> {
+    let mut lints = vec![];
+    for element in html.tree.root().descendants().filter_map(ElementRef::wrap) {
+        let element = element.value();
+        let name = element.name();
+        let namespace = &element.name.ns;
+
+        if *namespace != ns!(html) {
+            let known = if *namespace == ns!(svg) {
+                SVG_ELEMENTS
+            } else if *namespace == ns!(mathml) {
+                MATHML_ELEMENTS
+            } else {
+                ""
+            };
+            if !contains(known, name) {
+                lints.push(lint(
+                    "unknown_names",
+                    &format!("unknown element '<{}>'", name),
+                ));
+            }
+            // Foreign elements have too many presentation attributes to check
+            continue;
+        }
+        if name.contains('-') {
+            continue;
+        }
+        if !contains(HTML_ELEMENTS, name) {
+            lints.push(lint(
+                "unknown_names",
+                &format!("unknown element '<{}>'", name),
+            ));
+            continue;
+        }
+
+        let specific = ELEMENT_ATTRIBUTES
+            .iter()
+            .find(|(element, _)| *element == name)
+            .map_or("", |(_, attributes)| *attributes);
+        for (attribute, _) in element.attrs() {
+            let known = contains(GLOBAL_ATTRIBUTES, attribute)
+                || contains(specific, attribute)
+                || is_event_handler(attribute)
+                || ALLOWED_PREFIXES
+                    .iter()
+                    .any(|prefix| attribute.starts_with(prefix))
+                || prefixes
+                    .iter()
+                    .any(|prefix| attribute.starts_with(&**prefix));
+            if !known {
+                lints.push(lint(
+                    "unknown_names",
+                    &format!("unknown attribute '{}' on '<{}>'", attribute, name),
+                ));
+            }
+        }
+    }
+    lints
+}
+
+fn contains(names: &str, name: &str) -> bool {
+    names.split_whitespace().any(|known| known == name)
+}
+
+/// Unit tests for strict element and attribute names
+#[cfg(test)]
+mod test_names {
+    use crate::names::check_names;
+
+    #[test]
+    fn test_elements() {
+        no_lints("<div><p>text</p><ul><li>a</li></ul><dialog open></dialog></div>");
+        no_lints("<my-counter count=\"1\"></my-counter>");
+        no_lints("<style>p { color: red; }</style><p>text</p>");
+        no_lints("<svg viewBox=\"0 0 1 1\"><path d=\"M0 0\"/></svg><math><mi>x</mi></math>");
+        lints("<center>text</center>", &["unknown element '<center>'"]);
+        lints(
+            "<svg><circel r=\"1\"/></svg>",
+            &["unknown element '<circel>'"],
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        no_lints("<a href=\"/\" class=\"link\" data-id=\"1\" aria-label=\"Home\">Home</a>");
+        no_lints("<button onclick=\"go()\" type=\"button\">Go</button>");
+        no_lints("<div data-rhtml-placeholder-0></div>");
+        lints(
+            "<div hreff=\"/\"></div><img scr=\"a.png\" alt=\"\">",
+            &[
+                "unknown attribute 'hreff' on '<div>'",
+                "unknown attribute 'scr' on '<img>'",
+            ],
+        );
+        lints(
+            "<div href=\"/\"></div>",
+            &["unknown attribute 'href' on '<div>'"],
+        );
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let html = "<div x-data=\"{}\" hx-get=\"/\"><button @click=\"go\">Go</button></div>";
+        assert_eq!(messages(html, &[]).len(), 3);
+        assert!(messages(html, &["x-", "hx-", "@"]).is_empty());
+    }
+
+    fn messages(html: &str, prefixes: &[&str]) -> Vec<String> {
+        let parsed = scraper::Html::parse_fragment(html);
+        let prefixes: Vec<_> = prefixes.iter().map(|prefix| prefix.to_string()).collect();
+        check_names(&parsed, &prefixes)
+            .into_iter()
+            .map(|lint| lint.message)
+            .collect()
+    }
+
+    fn lints(html: &str, expected: &[&str]) {
+        assert_eq!(messages(html, &[]), expected, "{}", html);
+    }
+
+    fn no_lints(html: &str) {
+        lints(html, &[]);
+    }
+}
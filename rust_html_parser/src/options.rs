@@ -0,0 +1,138 @@
+use scraper::node::Element;
+
+use crate::placeholders::DUMMY_PREFIX;
+
+/// Options given before the template literal, e.g.
+/// `rhtml!(in "ul", "<li>...</li>")`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TemplateOptions {
+    /// Parent element the template is validated in
+    pub context: Option<String>,
+    /// Lint levels set with `allow(...)`, `warn(...)` or `deny(...)`,
+    /// later entries take precedence
+    pub levels: Vec<(String, Level)>,
+    /// Attribute name prefixes allowed by `unknown_names`,
+    /// set with e.g. `prefixes("hx-", "@")`
+    pub prefixes: Vec<String>,
+    /// Whether to collapse whitespace in the rendered HTML, set
+    /// with `minify(true)`. Defaults to the crate config or `false`
+    pub minify: Option<bool>,
+}
+
+/// How a lint is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// Issue found by a lint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub name: &'static str,
+    pub message: String,
+}
+
+/// Creates a lint, shared by the lint modules
+pub fn lint(name: &'static str, message: &str) -> Lint {
+    Lint {
+        name,
+        message: message.to_string(),
+    }
+}
+
+/// Whether the element has the dummy attribute of a placeholder in its
+/// attribute list, e.g. `<div {attrs}>`, which could add any attribute
+pub fn has_dummy_attribute(element: &Element) -> bool {
+    element
+        .attrs()
+        .any(|(name, _)| name.starts_with("data-") && name.contains(DUMMY_PREFIX))
+}
+
+/// Event handler content attributes, e.g. `onclick`
+pub fn is_event_handler(attribute: &str) -> bool {
+    attribute
+        .strip_prefix("on")
+        .is_some_and(|event| !event.is_empty() && event.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+/// Lints that can be configured per template, with their default level
+pub const LINTS: [(&str, Level); 13] = [
+    // Elements the HTML parser closes, moves or inserts implicitly
+    ("fixups", Level::Deny),
+    // Static ids used twice or referenced but missing, see `ids.rs`
+    ("duplicate_ids", Level::Deny),
+    ("dangling_ids", Level::Deny),
+    // Accessibility, see `a11y.rs`
+    ("img_alt", Level::Warn),
+    ("input_label", Level::Warn),
+    ("button_text", Level::Warn),
+    ("aria", Level::Warn),
+    ("heading_order", Level::Warn),
+    ("html_lang", Level::Warn),
+    // Discouraged markup, see `practices.rs`
+    ("deprecated_elements", Level::Warn),
+    ("inline_handlers", Level::Warn),
+    ("target_blank", Level::Warn),
+    // Element and attribute names not in the HTML standard, see `names.rs`
+    ("unknown_names", Level::Allow),
+];
+
+/// Names for several lints at once
+pub const LINT_GROUPS: [(&str, &[&str]); 3] = [
+    (
+        "a11y",
+        &[
+            "img_alt",
+            "input_label",
+            "button_text",
+            "aria",
+            "heading_order",
+            "html_lang",
+        ],
+    ),
+    ("ids", &["duplicate_ids", "dangling_ids"]),
+    (
+        "practices",
+        &["deprecated_elements", "inline_handlers", "target_blank"],
+    ),
+];
+
+impl TemplateOptions {
+    /// Level of a lint for this template
+    pub fn level(&self, lint: &str) -> Level {
+        let default = LINTS
+            .iter()
+            .find(|(name, _)| *name == lint)
+            .map_or(Level::Deny, |(_, level)| *level);
+        self.levels
+            .iter()
+            .rev()
+            .find(|(name, _)| name == lint)
+            .map_or(default, |(_, level)| *level)
+    }
+
+    pub fn set_level(&mut self, name: &str, level: Level) -> Result<(), String> {
+        if let Some((_, lints)) = LINT_GROUPS.iter().find(|(group, _)| *group == name) {
+            for lint in lints.iter() {
+                self.levels.push((lint.to_string(), level));
+            }
+            return Ok(());
+        }
+        if !LINTS.iter().any(|(lint, _)| *lint == name) {
+            let names: Vec<_> = LINTS
+                .iter()
+                .map(|(lint, _)| *lint)
+                .chain(LINT_GROUPS.iter().map(|(group, _)| *group))
+                .collect();
+            return Err(format!(
+                "unknown lint '{}', expected one of: {}",
+                name,
+                names.join(", ")
+            ));
+        }
+        self.levels.push((name.to_string(), level));
+        Ok(())
+    }
+}
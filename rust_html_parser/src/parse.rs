@@ -0,0 +1,623 @@
+use html5ever::{
+    interface::{QualName, QuirksMode},
+    tokenizer::TokenizerOpts,
+    tree_builder::TreeBuilderOpts,
+    LocalName, Namespace, ParseOpts,
+};
+use scraper::{HtmlTreeSink, Node};
+use tendril::TendrilSink;
+
+use crate::a11y::check_accessibility;
+use crate::elements::{MATHML_ELEMENTS, SVG_ELEMENTS};
+use crate::fixups::{find_fixups, Fixup};
+use crate::ids::check_ids;
+use crate::names::check_names;
+use crate::options::{lint, Level, Lint, TemplateOptions, LINTS};
+use crate::practices::check_practices;
+
+macro_rules! qual_name {
+    ($container:tt) => {
+        QualName::new(None, ns!(html), local_name!($container))
+    };
+    ($namespace:tt, $container:tt) => {
+        QualName::new(None, ns!($namespace), local_name!($container))
+    };
+}
+
+macro_rules! return_if_valid {
+    ($context:expr, $html:ident, $container:tt) => {{
+        let Err(error) = fragment_inside(qual_name!($container), $html) else {
+            return Ok($context);
+        };
+        error
+    }};
+}
+
+/// Parent context a template was successfully validated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlContext {
+    Body,
+    Document,
+    Html,
+    Table,
+    Tbody,
+    Tr,
+    /// Foreign content inside `<svg>`, e.g. a standalone `<path/>`
+    Svg,
+    /// Foreign content inside `<math>`
+    Math,
+    /// Explicit parent element given with `in "..."`
+    Custom,
+}
+
+/// Successfully validated HTML
+#[derive(Debug)]
+pub struct CheckedHtml {
+    pub context: HtmlContext,
+    /// Lints reported as warnings
+    pub warnings: Vec<Lint>,
+}
+
+/// Validates the HTML syntax and runs the lints at the levels set in
+/// the options. Returns the error message if the HTML is invalid or
+/// a lint is denied.
+pub fn check_html(html: &str, options: &TemplateOptions) -> Result<CheckedHtml, String> {
+    let result = match options.context.as_deref() {
+        Some(context) => validate_html_in(context, html),
+        None => validate_html(html),
+    };
+    let context = match result {
+        Ok(context) => context,
+        Err(errors) => {
+            return Err(format!(
+                "invalid HTML syntax ({} issues):\n{}",
+                errors.len(),
+                errors.join("\n")
+            ));
+        }
+    };
+
+    // Lints on the tree as parsed in the validated context
+    let parsed = parse_in_context(html, context, options.context.as_deref());
+    let document = matches!(context, HtmlContext::Document | HtmlContext::Html);
+    let mut lints: Vec<_> = find_fixups(html, &parsed, document)
+        .iter()
+        .map(|fixup| lint("fixups", &fixup.to_string()))
+        .collect();
+    lints.extend(check_accessibility(
+        &parsed,
+        context == HtmlContext::Document,
+    ));
+    lints.extend(check_ids(&parsed));
+    lints.extend(check_practices(&parsed));
+    lints.extend(check_names(&parsed, &options.prefixes));
+
+    let mut errors = vec![];
+    let mut warnings = vec![];
+    for lint in lints {
+        debug_assert!(LINTS.iter().any(|(name, _)| *name == lint.name));
+        match options.level(lint.name) {
+            Level::Allow => {}
+            Level::Warn => warnings.push(lint),
+            Level::Deny => errors.push(format!("{} ({})", lint.message, lint.name)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(format!(
+            "HTML lints failed ({} issues), use e.g. `allow(fixups)` or `warn(img_alt)` to relax:\n{}",
+            errors.len(),
+            errors.join("\n")
+        ));
+    }
+    Ok(CheckedHtml { context, warnings })
+}
+
+fn validate_html(html: &str) -> Result<HtmlContext, Vec<String>> {
+    // Check as body fragment or document root
+    let fragment = scraper::Html::parse_fragment(html);
+    if fragment.errors.is_empty() {
+        return Ok(HtmlContext::Body);
+    };
+    if scraper::Html::parse_document(html).errors.is_empty() {
+        return Ok(HtmlContext::Document);
+    };
+
+    // Custom fragments
+    return_if_valid!(HtmlContext::Html, html, "html");
+    return_if_valid!(HtmlContext::Tbody, html, "tbody");
+    return_if_valid!(HtmlContext::Table, html, "table");
+    return_if_valid!(HtmlContext::Tr, html, "tr");
+
+    // Foreign content fragments, e.g. a standalone `<path/>`
+    if foreign_fragment_inside(qual_name!(svg, "svg"), html, &ns!(svg), SVG_ELEMENTS) {
+        return Ok(HtmlContext::Svg);
+    }
+    if foreign_fragment_inside(
+        qual_name!(mathml, "math"),
+        html,
+        &ns!(mathml),
+        MATHML_ELEMENTS,
+    ) {
+        return Ok(HtmlContext::Math);
+    }
+
+    Err(fragment
+        .errors
+        .into_iter()
+        .map(|err| err.to_string())
+        .collect())
+}
+
+/// Validates a fragment inside an explicit parent element, e.g. `<li>`
+/// inside `ul`. Elements the parser would insert implicitly, like the
+/// `<tbody>` around a `<tr>` inside `table`, are errors.
+fn validate_html_in(context: &str, html: &str) -> Result<HtmlContext, Vec<String>> {
+    let (context_name, html_context) = match context {
+        "svg" => (qual_name!(svg, "svg"), HtmlContext::Svg),
+        "math" => (qual_name!(mathml, "math"), HtmlContext::Math),
+        name => (
+            QualName::new(None, ns!(html), LocalName::from(name)),
+            HtmlContext::Custom,
+        ),
+    };
+    let parsed = parse_fragment_inside(context_name, html);
+    if !parsed.errors.is_empty() {
+        return Err(parsed.errors.iter().map(|err| err.to_string()).collect());
+    }
+    let known_foreign = match html_context {
+        HtmlContext::Svg => known_foreign_elements(&parsed, &ns!(svg), SVG_ELEMENTS),
+        HtmlContext::Math => known_foreign_elements(&parsed, &ns!(mathml), MATHML_ELEMENTS),
+        _ => true,
+    };
+    if !known_foreign {
+        return Err(vec![format!("unknown element inside '<{}>'", context)]);
+    }
+
+    // Every element in the tree must come from a tag in the template
+    let errors: Vec<_> = find_fixups(html, &parsed, false)
+        .into_iter()
+        .filter_map(|fixup| match fixup {
+            Fixup::Inserted(element) => Some(format!(
+                "'<{}>' is inserted implicitly inside '<{}>', add it to the template or change the context",
+                element, context
+            )),
+            _ => None,
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(html_context)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validates HTML rendered at runtime that must be a single element,
+/// e.g. an htmx out-of-band fragment. Returns the (lowercase) names
+/// of the attributes of the root element.
+pub fn single_root_attributes(html: &str) -> Result<Vec<String>, String> {
+    let context = validate_html(html).map_err(|errors| {
+        format!(
+            "invalid HTML syntax ({} issues):\n{}",
+            errors.len(),
+            errors.join("\n")
+        )
+    })?;
+    if matches!(context, HtmlContext::Document | HtmlContext::Html) {
+        return Err("fragment must be an element, not a document".to_string());
+    }
+    let parsed = parse_in_context(html, context, None);
+    let mut roots = vec![];
+    for node in parsed.root_element().children() {
+        match node.value() {
+            Node::Element(element) => roots.push(element),
+            Node::Text(text) if text.trim().is_empty() => {}
+            _ => return Err("fragment must have a single root element".to_string()),
+        }
+    }
+    let [root] = roots[..] else {
+        return Err("fragment must have a single root element".to_string());
+    };
+    Ok(root.attrs().map(|(name, _)| name.to_string()).collect())
+}
+
+/// Parses a template in the context it was validated in
+fn parse_in_context(html: &str, context: HtmlContext, custom: Option<&str>) -> scraper::Html {
+    let context_name = match context {
+        HtmlContext::Document => return scraper::Html::parse_document(html),
+        HtmlContext::Body => qual_name!("body"),
+        HtmlContext::Html => qual_name!("html"),
+        HtmlContext::Table => qual_name!("table"),
+        HtmlContext::Tbody => qual_name!("tbody"),
+        HtmlContext::Tr => qual_name!("tr"),
+        HtmlContext::Svg => qual_name!(svg, "svg"),
+        HtmlContext::Math => qual_name!(mathml, "math"),
+        HtmlContext::Custom => {
+            QualName::new(None, ns!(html), LocalName::from(custom.unwrap_or_default()))
+        }
+    };
+    parse_fragment_inside(context_name, html)
+}
+
+/// Checks if a placeholder after `html_before` is the start of an `href`
+/// value on an SVG element, where the value must be a safe URL
+pub fn is_svg_url_attribute(html_before: &str, svg_fragment: bool) -> bool {
+    let Some(before_value) = html_before
+        .strip_suffix('"')
+        .or_else(|| html_before.strip_suffix('\''))
+    else {
+        return false;
+    };
+    let Some(before_equals) = before_value.trim_end().strip_suffix('=') else {
+        return false;
+    };
+    let before_equals = before_equals.trim_end();
+    let name_start = before_equals
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_')))
+        .map_or(0, |index| index + 1);
+    let name = before_equals[name_start..].to_ascii_lowercase();
+    if name != "href" && name != "xlink:href" {
+        return false;
+    }
+    // The attribute must be inside a start tag
+    let Some(tag_start) = before_equals.rfind('<') else {
+        return false;
+    };
+    if before_equals[tag_start..].contains('>') {
+        return false;
+    }
+    svg_fragment || svg_depth(&before_equals[..tag_start]) > 0
+}
+
+/// Number of open `<svg>` elements at the end of the HTML
+fn svg_depth(html: &str) -> usize {
+    let html = html.to_ascii_lowercase();
+    let is_tag = |rest: &str| rest.starts_with(|c: char| c.is_ascii_whitespace() || c == '>');
+    let opened = html
+        .match_indices("<svg")
+        .filter(|(i, _)| is_tag(&html[i + 4..]))
+        .count();
+    let closed = html
+        .match_indices("</svg")
+        .filter(|(i, _)| is_tag(&html[i + 5..]))
+        .count();
+    opened.saturating_sub(closed)
+}
+
+pub fn fragment_inside(context: QualName, fragment: &str) -> Result<(), Vec<String>> {
+    let html = parse_fragment_inside(context, fragment);
+    if html.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(html.errors.into_iter().map(|v| v.to_string()).collect())
+    }
+}
+
+/// Validates a fragment in a foreign context (`svg` or `math`). All
+/// foreign elements must be known, since any other element would
+/// be accepted as foreign content too.
+fn foreign_fragment_inside(
+    context: QualName,
+    fragment: &str,
+    namespace: &Namespace,
+    elements: &str,
+) -> bool {
+    let html = parse_fragment_inside(context, fragment);
+    html.errors.is_empty() && known_foreign_elements(&html, namespace, elements)
+}
+
+/// Checks that all elements in the namespace have a known name
+fn known_foreign_elements(html: &scraper::Html, namespace: &Namespace, elements: &str) -> bool {
+    html.tree.values().all(|node| match node.as_element() {
+        Some(element) if element.name.ns == *namespace => elements
+            .split_whitespace()
+            .any(|name| name == element.name()),
+        _ => true,
+    })
+}
+
+fn parse_fragment_inside(context: QualName, fragment: &str) -> scraper::Html {
+    let parser = html5ever::driver::parse_fragment(
+        HtmlTreeSink::new(scraper::Html::new_fragment()),
+        ParseOpts {
+            tokenizer: TokenizerOpts {
+                exact_errors: true,
+                ..Default::default()
+            },
+            tree_builder: TreeBuilderOpts {
+                exact_errors: true,
+                scripting_enabled: true,
+                quirks_mode: QuirksMode::Quirks,
+                ..Default::default()
+            },
+        },
+        context,
+        Vec::new(),
+    );
+    parser.one(fragment)
+}
+
+/// Unit tests for HTML validation
+#[cfg(test)]
+mod test_html_validation {
+    use crate::parse::{
+        is_svg_url_attribute, single_root_attributes, validate_html, validate_html_in, HtmlContext,
+    };
+
+    #[test]
+    fn test_root() {
+        valid(
+            r#"
+            <!DOCTYPE html>
+            <html>
+                <head></head>
+                <body></body>
+            </html>
+        "#,
+        );
+        valid("<!DOCTYPE html><html></html>");
+        invalid("<html></html>");
+        valid("<body></body>");
+    }
+
+    #[test]
+    fn test_div() {
+        valid("<div>hello</div>");
+        valid("<div class='hi'>hello</div>");
+        invalid("<div/>");
+    }
+
+    #[test]
+    fn test_button() {
+        valid("<button>hello</button>");
+        valid("<button></button>");
+        invalid("<button/>");
+    }
+
+    #[test]
+    fn test_br() {
+        valid("<br/>");
+        valid("<br><br>");
+        invalid("<br></br>");
+    }
+
+    #[test]
+    fn test_table() {
+        valid(
+            r#"
+            <table>
+                <tr><th>hi</th></tr>
+                <tr><td>hi</td></tr>
+            </table>
+        "#,
+        );
+        valid(
+            r#"
+            <table>
+                <thead>
+                    <tr><th>hi</th></tr>
+                    <tr><th>hi</th></tr>
+                </thead>
+                <tbody>
+                    <tr><td>hi</td></tr>
+                    <tr><td>hi</td></tr>
+                </tbody>
+            </table>
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_table_rows() {
+        valid(
+            r#"
+            <tr>
+                <td>hi world</td>
+                <td>hi world</td>
+            </tr>
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_table_fragments() {
+        valid("<table></table>");
+        valid("<thead></thead>");
+        valid("<tbody></tbody>");
+        valid("<tr></tr>");
+        valid("<td>Table content</td>");
+        valid("<th>Table content</th>");
+    }
+
+    #[test]
+    fn test_script() {
+        valid("<script></script>");
+        valid("<script>alert('hi')</script>");
+    }
+
+    #[test]
+    fn test_bad_attributes() {
+        invalid("<div class='bad_class></div>");
+        invalid("<div class=bad class></div>");
+        invalid("<div class=></div>");
+    }
+
+    #[test]
+    fn test_article() {
+        valid("<article></article>");
+    }
+
+    #[test]
+    fn test_fonts() {
+        valid("<b>hello</b>");
+        valid("<i>hello</i>");
+        valid("<b><i>hello</i></b>");
+    }
+
+    #[test]
+    fn test_iframe() {
+        valid("<iframe></iframe>");
+    }
+
+    #[test]
+    fn test_video() {
+        valid("<video></video>");
+    }
+
+    #[test]
+    fn test_input_label() {
+        valid("<input />");
+        valid("<label></label>");
+        valid("<label><input/></label>");
+    }
+
+    #[test]
+    fn test_comment() {
+        valid("<!-- comment --><div></div>");
+    }
+
+    #[test]
+    fn test_svg() {
+        valid(
+            r##"
+            <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" preserveAspectRatio="none">
+                <path d="M0 0h24v24H0z" fill="none"/>
+                <circle cx="12" cy="12" r="4"/>
+                <use href="#icon"/>
+            </svg>
+        "##,
+        );
+        valid("<svg><g><rect/><text x='1'>label</text></g></svg>");
+        valid("<svg><foreignObject><div>html</div></foreignObject></svg>");
+        invalid("<svg><path d='M0 0></svg>");
+        invalid("<button/>");
+        invalid("<div/><path/>");
+    }
+
+    #[test]
+    fn test_svg_fragments() {
+        assert_context("<path d=\"M0 0\"/>", HtmlContext::Svg);
+        assert_context("<circle r=\"1\"/><rect width=\"2\"/>", HtmlContext::Svg);
+        assert_context("<g><path/></g>", HtmlContext::Svg);
+        assert_context("<svg><path/></svg>", HtmlContext::Body);
+        invalid("<path d='M0 0>");
+    }
+
+    #[test]
+    fn test_single_root() {
+        let mut attributes =
+            single_root_attributes(" <div Class=\"a\" title='<b>'>text</div>\n").unwrap();
+        attributes.sort();
+        assert_eq!(attributes, ["class", "title"]);
+        assert!(single_root_attributes("<div><!-- <div> --></div>").is_ok());
+        assert!(single_root_attributes("<div><script>\"</div>\"</script></div>").is_ok());
+        assert!(single_root_attributes("<tr><td>cell</td></tr>").is_ok());
+        assert!(single_root_attributes("<path d=\"M0 0\"/>").is_ok());
+        for fragment in [
+            "text",
+            "<br><br>",
+            "<div></div> text",
+            "<!-- a --><div></div>",
+            "<!DOCTYPE html><html></html>",
+        ] {
+            let error = single_root_attributes(fragment).unwrap_err();
+            assert!(error.contains("root element") || error.contains("document"));
+        }
+        let error = single_root_attributes("<div class=></div>").unwrap_err();
+        assert!(error.contains("invalid HTML syntax"));
+    }
+
+    #[test]
+    fn test_svg_url_attribute() {
+        assert!(is_svg_url_attribute("<svg><a href=\"", false));
+        assert!(is_svg_url_attribute(
+            "<svg>\n<use class='x' xlink:href = '",
+            false
+        ));
+        assert!(is_svg_url_attribute("<use href=\"", true));
+        assert!(!is_svg_url_attribute("<a href=\"", false));
+        assert!(!is_svg_url_attribute("<svg></svg><a href=\"", false));
+        assert!(!is_svg_url_attribute("<svg><a href=\"#", false));
+        assert!(!is_svg_url_attribute("<svg><a data-href=\"", false));
+        assert!(!is_svg_url_attribute("<svg><a>href=\"", false));
+    }
+
+    #[test]
+    fn test_mathml() {
+        valid("<math><mi>x</mi><mo>=</mo><mn>2</mn></math>");
+        valid("<math><mfrac><mi>a</mi><mi>b</mi></mfrac><mspace/></math>");
+        assert_context("<mi>x</mi><mspace/>", HtmlContext::Math);
+    }
+
+    #[test]
+    fn test_context() {
+        valid_in("ul", "<li>one</li><li>two</li>");
+        valid_in(
+            "select",
+            "<option>a</option><optgroup><option>b</option></optgroup>",
+        );
+        valid_in("video", "<source src=\"a.mp4\"><track src=\"a.vtt\">");
+        valid_in("colgroup", "<col><col span=\"2\">");
+        valid_in("dl", "<dt>term</dt><dd>definition</dd>");
+        valid_in("tbody", "<tr><td>cell</td></tr>");
+        valid_in("head", "<title>a < b</title><script>if (a<b) {}</script>");
+        assert_eq!(validate_html_in("svg", "<path/>"), Ok(HtmlContext::Svg));
+        invalid_in("svg", "<div/>");
+        invalid_in("select", "<div></div>");
+        invalid_in("ul", "<li class='open>item</li>");
+    }
+
+    #[test]
+    fn test_context_implicit_elements() {
+        invalid_in("table", "<tr><td>cell</td></tr>");
+        invalid_in("tbody", "<td>cell</td>");
+        invalid_in("colgroup", "<col><td>cell</td>");
+        valid_in("table", "<tbody><tr><td>cell</td></tr></tbody>");
+    }
+
+    #[test]
+    fn test_full_website() {
+        valid(include_str!("./test.html"));
+    }
+
+    fn valid(html: &str) {
+        let trimmed = crate::template::trim_whitespace_per_line(html);
+        let result = validate_html(&trimmed);
+        assert!(
+            result.is_ok(),
+            "{} is invalid: {}",
+            &trimmed,
+            result.unwrap_err().join(", ")
+        );
+    }
+
+    fn assert_context(html: &str, context: HtmlContext) {
+        assert_eq!(validate_html(html), Ok(context), "{}", html);
+    }
+
+    fn valid_in(context: &str, html: &str) {
+        let result = validate_html_in(context, html);
+        assert!(
+            result.is_ok(),
+            "{} is invalid in '{}': {}",
+            html,
+            context,
+            result.unwrap_err().join(", ")
+        );
+    }
+
+    fn invalid_in(context: &str, html: &str) {
+        let result = validate_html_in(context, html);
+        assert!(
+            result.is_err(),
+            "Expected not valid in '{}': {}",
+            context,
+            html
+        );
+    }
+
+    fn invalid(html: &str) {
+        let trimmed = crate::template::trim_whitespace_per_line(html);
+        let result = validate_html(&trimmed);
+        assert!(result.is_err(), "Expected not valid: {}", html);
+    }
+}
@@ -0,0 +1,370 @@
+use crate::fixups::VOID_ELEMENTS;
+
+/// Prefix of the dummy text substituted for placeholders in text, comments
+/// and attribute values, and of the dummy attribute in `<div {attrs}>`.
+/// Lints skip values containing it, since they are only known at runtime.
+pub const DUMMY_PREFIX: &str = "rhtml-placeholder-";
+
+/// Substituted for placeholders between the child elements of an element
+/// that can't contain text, e.g. `<tbody>{rows}</tbody>`. The placeholder
+/// must render child elements there, which a comment stands in for.
+const DUMMY_CHILDREN: &str = "<!---->";
+
+/// Elements that only accept child elements
+const ELEMENT_ONLY_PARENTS: [&str; 8] = [
+    "html", "head", "table", "thead", "tbody", "tfoot", "tr", "colgroup",
+];
+
+/// Elements where the HTML parser reads everything up to the end tag as text
+pub const RAW_TEXT_ELEMENTS: [&str; 5] = ["script", "style", "textarea", "title", "xmp"];
+
+/// Position of a placeholder in the HTML
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    Text,
+    Comment,
+    /// Inside `<script>`, `<style>` etc. until the end tag
+    RawText(String),
+    /// Reading the name of a start tag
+    TagName(String),
+    /// Between attributes of a start tag, e.g. `<div {attrs}>`
+    Tag(String),
+    /// After `=` before an attribute value
+    BeforeValue(String),
+    QuotedValue(String, char),
+    UnquotedValue(String),
+    /// End tag, doctype or other markup declaration
+    OtherTag,
+}
+
+/// Joins the HTML parts with a dummy value for each placeholder, so
+/// that the validated HTML has the structure rendered at runtime.
+/// Placeholders in positions that can't be filled safely, like
+/// unquoted attribute values or tag names, are left out.
+pub fn html_with_dummies(html_parts: &[String]) -> String {
+    let mut html = String::new();
+    let mut scanner = Scanner::default();
+    for (index, part) in html_parts.iter().enumerate() {
+        html.push_str(part);
+        scanner.scan(part);
+        if index + 1 == html_parts.len() {
+            break;
+        }
+        let dummy = match &scanner.state {
+            State::Text => {
+                let following = html_parts[index + 1..].concat();
+                let following = following.trim_start();
+                let between_tags =
+                    scanner.text_is_blank && (following.is_empty() || following.starts_with('<'));
+                match scanner.open_elements.last() {
+                    Some(parent)
+                        if between_tags && ELEMENT_ONLY_PARENTS.contains(&parent.as_str()) =>
+                    {
+                        DUMMY_CHILDREN.to_string()
+                    }
+                    _ => format!("{}{}", DUMMY_PREFIX, index),
+                }
+            }
+            State::Comment | State::RawText(_) | State::QuotedValue(..) => {
+                format!("{}{}", DUMMY_PREFIX, index)
+            }
+            State::Tag(_) => format!("data-{}{}", DUMMY_PREFIX, index),
+            State::TagName(_)
+            | State::BeforeValue(_)
+            | State::UnquotedValue(_)
+            | State::OtherTag => String::new(),
+        };
+        html.push_str(&dummy);
+    }
+    html
+}
+
+/// Whether each placeholder is inside a quoted attribute value, where
+/// it is rendered with `Render::render_attribute`
+pub fn quoted_value_placeholders(html_parts: &[String]) -> Vec<bool> {
+    let mut scanner = Scanner::default();
+    let Some((_, parts)) = html_parts.split_last() else {
+        return vec![];
+    };
+    parts
+        .iter()
+        .map(|part| {
+            scanner.scan(part);
+            matches!(scanner.state, State::QuotedValue(..))
+        })
+        .collect()
+}
+
+/// Minimal HTML tokenizer tracking the position of placeholders,
+/// also used to rewrite the HTML between them (see `scoped.rs` and
+/// `minify.rs`). The state carries over from one part to the next.
+pub struct Scanner {
+    state: State,
+    /// Elements opened and not yet closed, by lowercase name
+    open_elements: Vec<String>,
+    /// Whether the text since the last tag is only whitespace
+    text_is_blank: bool,
+    /// The two characters before the current one
+    previous: [char; 2],
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Scanner {
+            state: State::Text,
+            open_elements: vec![],
+            text_is_blank: true,
+            previous: [' '; 2],
+        }
+    }
+}
+
+impl Scanner {
+    /// Closes the element named at the start of `end_tag`,
+    /// along with the elements opened inside it
+    fn close_element(&mut self, end_tag: &str) {
+        let name: String = end_tag
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if let Some(index) = self.open_elements.iter().rposition(|open| *open == name) {
+            self.open_elements.truncate(index);
+        }
+    }
+
+    fn scan(&mut self, part: &str) {
+        for (index, c) in part.char_indices() {
+            self.advance(c, &part[index + c.len_utf8()..]);
+        }
+    }
+
+    /// Moves past the character `c`, followed by `rest` in the same part
+    pub fn advance(&mut self, c: char, rest: &str) {
+        self.next(c, rest);
+        self.previous = [self.previous[1], c];
+    }
+
+    /// Whether the scanner is in text content, outside of tags,
+    /// comments and raw text elements
+    pub fn in_text(&self) -> bool {
+        self.state == State::Text
+    }
+
+    /// Whether the scanner is reading the name of a start tag
+    pub fn in_tag_name(&self) -> bool {
+        matches!(self.state, State::TagName(_))
+    }
+
+    /// Whether an element with the lowercase name is open
+    pub fn inside(&self, name: &str) -> bool {
+        self.open_elements.iter().any(|open| open == name)
+    }
+
+    fn next(&mut self, c: char, rest: &str) {
+        let state = std::mem::replace(&mut self.state, State::Text);
+        self.state = match state {
+            State::Text if c == '<' => {
+                if rest.starts_with("!--") {
+                    State::Comment
+                } else if rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                    // A placeholder right after '<' would be the tag name
+                    State::TagName(String::new())
+                } else if let Some(end_tag) = rest.strip_prefix('/') {
+                    self.close_element(end_tag);
+                    State::OtherTag
+                } else if rest.starts_with(['!', '?']) {
+                    State::OtherTag
+                } else {
+                    self.text_is_blank = false;
+                    State::Text
+                }
+            }
+            State::Text => {
+                if !c.is_whitespace() {
+                    self.text_is_blank = false;
+                }
+                State::Text
+            }
+            State::Comment if c == '>' && self.previous == ['-', '-'] => {
+                self.text_is_blank = true;
+                State::Text
+            }
+            State::Comment => State::Comment,
+            State::RawText(name) => {
+                let end_tag = rest
+                    .strip_prefix('/')
+                    .and_then(|end| end.get(..name.len()))
+                    .is_some_and(|end| end.eq_ignore_ascii_case(&name));
+                if c == '<' && end_tag {
+                    self.close_element(&name);
+                    State::OtherTag
+                } else {
+                    State::RawText(name)
+                }
+            }
+            State::TagName(mut name) => match c {
+                '>' => self.close_start_tag(name, false),
+                '/' => State::Tag(name),
+                c if c.is_whitespace() => State::Tag(name),
+                c => {
+                    name.push(c.to_ascii_lowercase());
+                    State::TagName(name)
+                }
+            },
+            State::Tag(name) => match c {
+                '>' => {
+                    let self_closing = self.previous[1] == '/';
+                    self.close_start_tag(name, self_closing)
+                }
+                '=' => State::BeforeValue(name),
+                _ => State::Tag(name),
+            },
+            State::BeforeValue(name) => match c {
+                '"' | '\'' => State::QuotedValue(name, c),
+                '>' => self.close_start_tag(name, false),
+                c if c.is_whitespace() => State::BeforeValue(name),
+                _ => State::UnquotedValue(name),
+            },
+            State::QuotedValue(name, quote) if c == quote => State::Tag(name),
+            State::QuotedValue(name, quote) => State::QuotedValue(name, quote),
+            State::UnquotedValue(name) => match c {
+                '>' => self.close_start_tag(name, false),
+                c if c.is_whitespace() => State::Tag(name),
+                _ => State::UnquotedValue(name),
+            },
+            State::OtherTag if c == '>' => {
+                self.text_is_blank = true;
+                State::Text
+            }
+            State::OtherTag => State::OtherTag,
+        };
+    }
+
+    fn close_start_tag(&mut self, name: String, self_closing: bool) -> State {
+        self.text_is_blank = true;
+        if !self_closing && !VOID_ELEMENTS.split_whitespace().any(|void| void == name) {
+            self.open_elements.push(name.clone());
+        }
+        if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+            State::RawText(name)
+        } else {
+            State::Text
+        }
+    }
+}
+
+/// Unit tests for placeholder substitution
+#[cfg(test)]
+mod test_placeholders {
+    use crate::options::TemplateOptions;
+    use crate::parse::check_html;
+    use crate::placeholders::{html_with_dummies, quoted_value_placeholders};
+
+    #[test]
+    fn test_text() {
+        assert_dummies(&["<p>Hello ", "!</p>"], "<p>Hello rhtml-placeholder-0!</p>");
+        assert_dummies(&["<div>", "</div>"], "<div>rhtml-placeholder-0</div>");
+        assert_dummies(&["", "", ""], "rhtml-placeholder-0rhtml-placeholder-1");
+        assert_dummies(&["<!-- ", " -->"], "<!-- rhtml-placeholder-0 -->");
+        assert_dummies(
+            &["<script>let a = ", ";</script>"],
+            "<script>let a = rhtml-placeholder-0;</script>",
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        assert_dummies(
+            &["<div class=\"a ", " b\" title='", "'>"],
+            "<div class=\"a rhtml-placeholder-0 b\" title='rhtml-placeholder-1'>",
+        );
+        assert_dummies(
+            &["<div ", " ", ">"],
+            "<div data-rhtml-placeholder-0 data-rhtml-placeholder-1>",
+        );
+        assert_dummies(&["<div class=", "></div>"], "<div class=></div>");
+        assert_dummies(&["<", "></", ">"], "<></>");
+    }
+
+    #[test]
+    fn test_quoted_values() {
+        let parts = [
+            "<div class=\"a ",
+            " b\" title='",
+            "' ",
+            ">",
+            "</div><p x=",
+            ">",
+        ];
+        let parts: Vec<_> = parts.iter().map(|part| part.to_string()).collect();
+        assert_eq!(
+            quoted_value_placeholders(&parts),
+            [true, true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn test_element_only_parents() {
+        assert_dummies(
+            &["<table><tbody>\n", "\n</tbody></table>"],
+            "<table><tbody>\n<!---->\n</tbody></table>",
+        );
+        assert_dummies(&["<tr>", "", "</tr>"], "<tr><!----><!----></tr>");
+        assert_dummies(&["<head>", "</head>"], "<head><!----></head>");
+        assert_dummies(&["<tr>", "<td></td></tr>"], "<tr><!----><td></td></tr>");
+        assert_dummies(&["<tr><td></td>", "</tr>"], "<tr><td></td><!----></tr>");
+        assert_dummies(
+            &["<head><title>Page</title>\n", "\n</head>"],
+            "<head><title>Page</title>\n<!---->\n</head>",
+        );
+        assert_dummies(&["<tr>a ", "</tr>"], "<tr>a rhtml-placeholder-0</tr>");
+        assert_dummies(
+            &["<table><caption>", "</caption></table>"],
+            "<table><caption>rhtml-placeholder-0</caption></table>",
+        );
+        assert_dummies(
+            &["<table><tr><td><br></td>", "</tr></table>"],
+            "<table><tr><td><br></td><!----></tr></table>",
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        valid(&["<table><tbody>", "</tbody></table>"]);
+        valid(&["<div ", ">", "</div>"]);
+        valid(&["<ul>", "</ul>"]);
+        valid(&["<table><tbody><tr>", "<td></td></tr></tbody></table>"]);
+        valid(&[
+            "<!DOCTYPE html><html><head><title>Page</title>",
+            "</head></html>",
+        ]);
+        invalid(&["<table><tbody><tr>text ", "</tr></tbody></table>"]);
+        invalid(&["<div class=\"a\"", "></div>"]);
+        invalid(&["<div class=", "></div>"]);
+    }
+
+    fn assert_dummies(parts: &[&str], expected: &str) {
+        let parts: Vec<_> = parts.iter().map(|part| part.to_string()).collect();
+        assert_eq!(html_with_dummies(&parts), expected);
+    }
+
+    fn valid(parts: &[&str]) {
+        let parts: Vec<_> = parts.iter().map(|part| part.to_string()).collect();
+        let html = html_with_dummies(&parts);
+        let result = check_html(&html, &TemplateOptions::default());
+        assert!(
+            result.is_ok(),
+            "{} is invalid: {}",
+            html,
+            result.unwrap_err()
+        );
+    }
+
+    fn invalid(parts: &[&str]) {
+        let parts: Vec<_> = parts.iter().map(|part| part.to_string()).collect();
+        let html = html_with_dummies(&parts);
+        let result = check_html(&html, &TemplateOptions::default());
+        assert!(result.is_err(), "Expected not valid: {}", html);
+    }
+}
@@ -0,0 +1,125 @@
+use scraper::{node::Element, ElementRef};
+
+use crate::options::{has_dummy_attribute, is_event_handler, lint, Lint};
+use crate::placeholders::DUMMY_PREFIX;
+
+/// Obsolete elements, styled with CSS or replaced by other elements today
+const DEPRECATED_ELEMENTS: &str = "acronym applet basefont bgsound big blink center dir font \
+    frame frameset isindex keygen listing marquee menuitem multicol nextid nobr noembed \
+    noframes plaintext rb rtc spacer strike tt xmp";
+
+/// Checks for markup that works, but is discouraged: deprecated elements,
+/// inline event handlers and `target="_blank"` links without `rel="noopener"`
+pub fn check_practices(html: &scraper::Html) -> Vec<Lint> {
+    let mut lints = vec![];
+    for element in html.tree.root().descendants().filter_map(ElementRef::wrap) {
+        let element = element.value();
+        let name = element.name();
+
+        if *element.name.ns == *ns!(html)
+            && DEPRECATED_ELEMENTS
+                .split_whitespace()
+                .any(|deprecated| deprecated == name)
+        {
+            lints.push(lint(
+                "deprecated_elements",
+                &format!("'<{}>' is deprecated, use CSS or a current element", name),
+            ));
+        }
+
+        for (attribute, _) in element.attrs() {
+            if is_event_handler(attribute) {
+                lints.push(lint(
+                    "inline_handlers",
+                    &format!(
+                        "inline event handler '{}' on '<{}>', use addEventListener in a script",
+                        attribute, name
+                    ),
+                ));
+            }
+        }
+
+        if opens_new_context(element) && !has_noopener(element) {
+            lints.push(lint(
+                "target_blank",
+                &format!(
+                    "'<{}>' with target=\"_blank\" should have rel=\"noopener\"",
+                    name
+                ),
+            ));
+        }
+    }
+    lints
+}
+
+/// Links and forms opening in a new browsing context
+fn opens_new_context(element: &Element) -> bool {
+    matches!(element.name(), "a" | "area" | "form")
+        && element
+            .attr("target")
+            .is_some_and(|target| target.eq_ignore_ascii_case("_blank"))
+}
+
+fn has_noopener(element: &Element) -> bool {
+    let rel = element.attr("rel").unwrap_or("");
+    has_dummy_attribute(element)
+        || rel.contains(DUMMY_PREFIX)
+        || rel.split_ascii_whitespace().any(|rel| {
+            rel.eq_ignore_ascii_case("noopener") || rel.eq_ignore_ascii_case("noreferrer")
+        })
+}
+
+/// Unit tests for discouraged markup
+#[cfg(test)]
+mod test_practices {
+    use crate::practices::check_practices;
+
+    #[test]
+    fn test_deprecated_elements() {
+        lints(
+            "<center><font color=\"red\">text</font></center>",
+            &["deprecated_elements", "deprecated_elements"],
+        );
+        no_lints("<div><span>text</span></div>");
+        no_lints("<svg><font></font></svg>");
+    }
+
+    #[test]
+    fn test_inline_handlers() {
+        lints(
+            "<button onclick=\"save()\">Save</button><img src=\"a.png\" alt=\"\" onError=\"retry()\">",
+            &["inline_handlers", "inline_handlers"],
+        );
+        no_lints("<button hx-on=\"click\" data-on=\"x\">Save</button>");
+    }
+
+    #[test]
+    fn test_target_blank() {
+        lints(
+            "<a href=\"https://example.com\" target=\"_blank\">Example</a>",
+            &["target_blank"],
+        );
+        lints(
+            "<form target=\"_BLANK\" rel=\"nofollow\"></form>",
+            &["target_blank"],
+        );
+        no_lints("<a href=\"/\" target=\"_blank\" rel=\"noopener\">Home</a>");
+        no_lints("<a href=\"/\" target=\"_blank\" rel=\"external noreferrer\">Home</a>");
+        no_lints("<a href=\"/\" target=\"_self\">Home</a>");
+        no_lints("<a href=\"/\" target=\"_blank\" rel=\"rhtml-placeholder-0\">Home</a>");
+        no_lints("<a href=\"/\" target=\"_blank\" data-rhtml-placeholder-0>Home</a>");
+    }
+
+    fn lints(html: &str, expected: &[&str]) {
+        let parsed = scraper::Html::parse_fragment(html);
+        let names: Vec<_> = check_practices(&parsed)
+            .into_iter()
+            .map(|lint| lint.name)
+            .collect();
+        assert_eq!(names, expected, "{}", html);
+    }
+
+    fn no_lints(html: &str) {
+        lints(html, &[]);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::placeholders::Scanner;
+
+/// Prefix of the attribute added to the elements of a template with a
+/// scoped style, followed by a hash of the CSS
+pub const SCOPE_PREFIX: &str = "data-rhtml-";
+
+/// At-rules containing style rules, e.g. `@media (...) { .a { ... } }`
+const GROUPING_RULES: [&str; 5] = ["media", "supports", "container", "layer", "scope"];
+
+/// CSS of the `<style scoped>` blocks of a template, with selectors
+/// limited to the elements of the template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopedStyle {
+    /// Attribute added to every element, e.g. `data-rhtml-1a2b3c4d`
+    pub attribute: String,
+    pub css: String,
+}
+
+/// Removes the `<style scoped>` blocks from the HTML parts, and adds the
+/// scope attribute to every element of the template
+pub fn scope_template(html_parts: &mut [String]) -> Result<Option<ScopedStyle>, String> {
+    let mut css = String::new();
+    for part in html_parts.iter_mut() {
+        while let Some((start, end, content)) = find_scoped_style(part)? {
+            css.push_str(content.trim());
+            css.push('\n');
+            part.replace_range(start..end, "");
+        }
+    }
+    if css.trim().is_empty() {
+        return Ok(None);
+    }
+    let attribute = format!("{}{:08x}", SCOPE_PREFIX, fnv_hash(&css));
+    let css = scope_css(css.trim_end(), &attribute)?;
+    add_attribute(html_parts, &attribute);
+    Ok(Some(ScopedStyle { attribute, css }))
+}
+
+/// Finds the next `<style scoped>` element: its start, end and CSS
+fn find_scoped_style(part: &str) -> Result<Option<(usize, usize, &str)>, String> {
+    let lowercase = part.to_ascii_lowercase();
+    let mut offset = 0;
+    while let Some(index) = lowercase[offset..].find("<style") {
+        let start = offset + index;
+        let Some(tag_length) = lowercase[start..].find('>') else {
+            return Ok(None);
+        };
+        let tag = &lowercase[start + "<style".len()..start + tag_length];
+        offset = start + tag_length + 1;
+        if !tag.starts_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let scoped = tag
+            .split(|c: char| c.is_ascii_whitespace() || c == '/')
+            .any(|attribute| attribute == "scoped");
+        if !scoped {
+            continue;
+        }
+        let Some(content_length) = lowercase[offset..].find("</style") else {
+            return Err(
+                "placeholders can't be used in a scoped <style>, the CSS is scoped at compile time"
+                    .to_string(),
+            );
+        };
+        let content_end = offset + content_length;
+        let end = lowercase[content_end..]
+            .find('>')
+            .map_or(part.len(), |length| content_end + length + 1);
+        return Ok(Some((start, end, &part[offset..content_end])));
+    }
+    Ok(None)
+}
+
+/// Adds the attribute after the name of every start tag
+fn add_attribute(html_parts: &mut [String], attribute: &str) {
+    let mut scanner = Scanner::default();
+    for part in html_parts.iter_mut() {
+        let mut output = String::with_capacity(part.len());
+        for (index, c) in part.char_indices() {
+            let name_end = c.is_whitespace() || c == '/' || c == '>';
+            if scanner.in_tag_name() && name_end {
+                output.push(' ');
+                output.push_str(attribute);
+            }
+            output.push(c);
+            scanner.advance(c, &part[index + c.len_utf8()..]);
+        }
+        *part = output;
+    }
+}
+
+/// Adds the attribute selector to every selector of the style rules
+pub fn scope_css(css: &str, attribute: &str) -> Result<String, String> {
+    let css = strip_comments(css);
+    let mut output = String::new();
+    let mut rest = css.as_str();
+    loop {
+        let Some(end) = find_top_level(rest, &['{', ';']) else {
+            output.push_str(rest.trim());
+            return Ok(output);
+        };
+        let prelude = rest[..end].trim();
+        if rest[end..].starts_with(';') {
+            // Statement at-rule, e.g. `@import "a.css";`
+            output.push_str(prelude);
+            output.push(';');
+            rest = &rest[end + 1..];
+            continue;
+        }
+        let block_end = find_block_end(&rest[end + 1..])
+            .ok_or("unclosed '{' in scoped <style>".to_string())?
+            + end
+            + 1;
+        let block = &rest[end + 1..block_end];
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            let name: String = at_rule
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+                .collect();
+            output.push_str(prelude);
+            output.push_str(" {");
+            if GROUPING_RULES.contains(&name.to_ascii_lowercase().as_str()) {
+                output.push_str(&scope_css(block, attribute)?);
+            } else {
+                // E.g. @keyframes or @font-face
+                output.push_str(block.trim());
+            }
+        } else {
+            output.push_str(&scope_selectors(prelude, attribute));
+            output.push_str(" {");
+            output.push_str(block.trim_end());
+        }
+        output.push_str("}\n");
+        rest = &rest[block_end + 1..];
+    }
+}
+
+/// Adds `[attribute]` to the last compound selector of each selector in
+/// the list, before any pseudo-class or pseudo-element
+fn scope_selectors(selectors: &str, attribute: &str) -> String {
+    split_top_level(selectors, ',')
+        .iter()
+        .map(|selector| {
+            let selector = selector.trim();
+            let compound_start = last_compound(selector);
+            let compound = &selector[compound_start..];
+            let insert_at = find_top_level(compound, &[':'])
+                .map_or(selector.len(), |index| compound_start + index);
+            format!(
+                "{}[{}]{}",
+                &selector[..insert_at],
+                attribute,
+                &selector[insert_at..]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Start of the last compound selector, after the last combinator
+fn last_compound(selector: &str) -> usize {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut quote = None;
+    for (index, c) in selector.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, '>' | '+' | '~') if depth == 0 => start = index + 1,
+            (None, c) if depth == 0 && c.is_whitespace() => start = index + c.len_utf8(),
+            _ => {}
+        }
+    }
+    start
+}
+
+/// Index of the first of the characters outside brackets and strings
+fn find_top_level(text: &str, characters: &[char]) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if depth == 0 && characters.contains(&c) => return Some(index),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut rest = text;
+    while let Some(index) = find_top_level(rest, &[separator]) {
+        parts.push(&rest[..index]);
+        rest = &rest[index + 1..];
+    }
+    parts.push(rest);
+    parts
+}
+
+/// Index of the `}` closing a block, given the text after its `{`
+fn find_block_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') if depth == 0 => return Some(index),
+            (None, '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+        rest = rest[start + 2..]
+            .find("*/")
+            .map_or("", |end| &rest[start + 2 + end + 2..]);
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 32-bit FNV-1a hash, stable across compiler versions
+fn fnv_hash(text: &str) -> u32 {
+    text.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+/// Unit tests for scoped styles
+#[cfg(test)]
+mod test_scoped {
+    use crate::scoped::{scope_css, scope_template};
+
+    #[test]
+    fn test_scope_selectors() {
+        assert_scoped(".a { color: red; }", ".a[s] { color: red;}\n");
+        assert_scoped(
+            "ul > li.item, p a:hover, h1::before { x: y }",
+            "ul > li.item[s], p a[s]:hover, h1[s]::before { x: y}\n",
+        );
+        assert_scoped(
+            "a[href^=\"http:\"] + span:not(.b, .c) { x: y }",
+            "a[href^=\"http:\"] + span[s]:not(.b, .c) { x: y}\n",
+        );
+        assert_scoped("* { margin: 0 }", "*[s] { margin: 0}\n");
+    }
+
+    #[test]
+    fn test_at_rules() {
+        assert_scoped(
+            "@import url(\"a.css\");\n@media (max-width: 600px) { .a { x: y } }",
+            "@import url(\"a.css\");@media (max-width: 600px) {.a[s] { x: y}\n}\n",
+        );
+        assert_scoped(
+            "@keyframes spin { from { x: 0 } to { x: 1 } }",
+            "@keyframes spin {from { x: 0 } to { x: 1 }}\n",
+        );
+        assert_scoped("/* a { } */ .b { x: \"}\" }", ".b[s] { x: \"}\"}\n");
+    }
+
+    #[test]
+    fn test_scope_template() {
+        let mut parts = vec![
+            "<style scoped>.title { color: red; }</style>\n<div class=\"card\">\n<h2 class=\"title\">"
+                .to_string(),
+            "</h2><script>if (a<b) {}</script><!-- <p> --><br/></div>".to_string(),
+        ];
+        let style = scope_template(&mut parts).unwrap().unwrap();
+        assert!(style.attribute.starts_with("data-rhtml-"));
+        assert_eq!(
+            style.css,
+            format!(".title[{}] {{ color: red;}}\n", style.attribute)
+        );
+        let expected = [
+            "\n<div {a} class=\"card\">\n<h2 {a} class=\"title\">",
+            "</h2><script {a}>if (a<b) {}</script><!-- <p> --><br {a}/></div>",
+        ]
+        .map(|part| part.replace("{a}", &style.attribute));
+        assert_eq!(parts, expected);
+    }
+
+    #[test]
+    fn test_scope_template_quoted_values() {
+        let mut parts = vec![
+            "<style scoped>p { color: red; }</style><p title=\"a > <b>\" class=c>".to_string(),
+            "</p>".to_string(),
+        ];
+        let style = scope_template(&mut parts).unwrap().unwrap();
+        let expected = format!("<p {} title=\"a > <b>\" class=c>", style.attribute);
+        assert_eq!(parts[0], expected);
+    }
+
+    #[test]
+    fn test_unscoped() {
+        let mut parts = vec!["<style>.a { x: y }</style><div></div>".to_string()];
+        assert_eq!(scope_template(&mut parts), Ok(None));
+        assert_eq!(parts, ["<style>.a { x: y }</style><div></div>"]);
+
+        let mut parts = vec![
+            "<style scoped>.a { x: ".to_string(),
+            " }</style>".to_string(),
+        ];
+        assert!(scope_template(&mut parts)
+            .unwrap_err()
+            .contains("placeholders"));
+    }
+
+    fn assert_scoped(css: &str, expected: &str) {
+        assert_eq!(scope_css(css, "s").unwrap(), expected);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::minify::minify_html_parts;
+use crate::options::{Lint, TemplateOptions};
+use crate::parse::{check_html, is_svg_url_attribute, CheckedHtml, HtmlContext};
+use crate::placeholders::{html_with_dummies, quoted_value_placeholders};
+use crate::scoped::{scope_template, ScopedStyle};
+
+/// Position of a placeholder in the HTML, deciding how its value is escaped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder {
+    Content,
+    /// Quoted attribute value, see `Render::render_attribute`
+    AttributeValue,
+    /// Attribute value that must be a safe URL, see `SafeUrl`
+    UrlAttribute,
+}
+
+/// Validated HTML of a template, ready to be rendered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedHtml {
+    /// HTML before each placeholder, and after the last one
+    pub html_parts: Vec<String>,
+    /// Position of each placeholder
+    pub placeholders: Vec<Placeholder>,
+    /// CSS of the `<style scoped>` blocks, removed from the HTML
+    pub scoped_css: Option<String>,
+    /// Lints reported as warnings
+    pub warnings: Vec<Lint>,
+}
+
+/// Splits template content into HTML parts and the source code of the
+/// placeholders between them. `{{` and `}}` escape a bracket in HTML.
+pub fn split_placeholders(content: &str) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut depth = 0;
+    let mut html_buffer = String::new();
+    let mut rust_buffer = String::new();
+    let mut html_parts = vec![];
+    let mut sources = vec![];
+    let mut chars = content.chars().peekable();
+
+    while let Some(token) = chars.next() {
+        match token {
+            // Escaping brackets
+            '{' if depth == 0 && chars.peek() == Some(&'{') => {
+                chars.next();
+                html_buffer.push('{');
+            }
+            '}' if depth == 0 && chars.peek() == Some(&'}') => {
+                chars.next();
+                html_buffer.push('}');
+            }
+            '{' => {
+                depth += 1;
+                if depth == 1 {
+                    html_parts.push(std::mem::take(&mut html_buffer));
+                } else {
+                    rust_buffer.push('{');
+                }
+            }
+            '}' => {
+                if depth == 0 {
+                    return Err(
+                        "Unexpected close bracket '}', need an open bracket first (or '}}' to escape)"
+                            .to_string(),
+                    );
+                }
+                depth -= 1;
+                if depth == 0 {
+                    sources.push(std::mem::take(&mut rust_buffer));
+                } else {
+                    rust_buffer.push('}');
+                }
+            }
+            token if depth > 0 => rust_buffer.push(token),
+            token => html_buffer.push(token),
+        }
+    }
+    if depth > 0 {
+        return Err("Missing close bracket '}'".to_string());
+    }
+    html_parts.push(html_buffer);
+    Ok((html_parts, sources))
+}
+
+/// Scopes the `<style scoped>` blocks, validates the HTML with a dummy
+/// value for each placeholder and finds the position of the placeholders.
+/// This is the same for templates checked by the macros at compile time
+/// and for `rhtml_file!` templates hot reloaded at runtime.
+pub fn prepare_html(
+    mut html_parts: Vec<String>,
+    options: &TemplateOptions,
+) -> Result<PreparedHtml, String> {
+    // Move `<style scoped>` CSS out of the HTML, see `Template::scoped_css`
+    let scoped_style = scope_template(&mut html_parts)?;
+
+    // Validate with a dummy value for each placeholder
+    let html_for_validate = trim_whitespace_per_line(&html_with_dummies(&html_parts));
+    let CheckedHtml { context, warnings } = check_html(&html_for_validate, options)?;
+
+    // Placeholders in attribute values, and those starting
+    // a URL attribute on an SVG element
+    let quoted_values = quoted_value_placeholders(&html_parts);
+    let mut placeholders = vec![];
+    let mut html_before = String::new();
+    for (html_part, quoted_value) in html_parts.iter().zip(quoted_values) {
+        html_before.push_str(html_part);
+        placeholders.push(
+            if is_svg_url_attribute(&html_before, context == HtmlContext::Svg) {
+                Placeholder::UrlAttribute
+            } else if quoted_value {
+                Placeholder::AttributeValue
+            } else {
+                Placeholder::Content
+            },
+        );
+    }
+
+    if options.minify == Some(true) {
+        html_parts = minify_html_parts(&html_parts);
+    }
+
+    Ok(PreparedHtml {
+        html_parts,
+        placeholders,
+        scoped_css: scoped_style.map(|ScopedStyle { css, .. }| css),
+        warnings,
+    })
+}
+
+/// Removes empty lines and whitespace surroinding each line
+/// Necessary for certain quirks of the html5ever parser
+///
+/// E.g <tr> rows must be trimmed this way
+/// since whitespace is parsed as text tokens.
+pub fn trim_whitespace_per_line(html: &str) -> String {
+    html.split('\n')
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_string()
+}
+
+/// Unit tests for template preparation
+#[cfg(test)]
+mod test_template {
+    use crate::options::{Level, TemplateOptions};
+    use crate::template::{prepare_html, split_placeholders, Placeholder};
+
+    #[test]
+    fn test_split_placeholders() {
+        let (html, sources) = split_placeholders("a{x}b{ if y { 1 } else { 2 } }{{c}}").unwrap();
+        assert_eq!(html, vec!["a", "b", "{c}"]);
+        assert_eq!(sources, vec!["x", " if y { 1 } else { 2 } "]);
+        assert!(split_placeholders("{x").is_err());
+        assert!(split_placeholders("x}").is_err());
+    }
+
+    #[test]
+    fn test_prepare_html() {
+        let prepared = prepare(
+            "<style scoped>p {{ color: red; }}</style><p title=\"{title}\">{content}</p>\
+            <svg><a href=\"{url}\"></a></svg>",
+            &TemplateOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            prepared.placeholders,
+            [
+                Placeholder::AttributeValue,
+                Placeholder::Content,
+                Placeholder::UrlAttribute
+            ]
+        );
+        assert!(prepared.html_parts[0].starts_with("<p data-rhtml-"));
+        assert!(prepared.scoped_css.unwrap().contains("color: red"));
+    }
+
+    #[test]
+    fn test_prepare_html_options() {
+        let html = "<div>\n    <img src=\"{src}\">\n</div>";
+        let prepared = prepare(html, &TemplateOptions::default()).unwrap();
+        assert_eq!(prepared.warnings.len(), 1);
+        assert_eq!(prepared.html_parts[1], "\">\n</div>");
+
+        let options = TemplateOptions {
+            levels: vec![("img_alt".to_string(), Level::Deny)],
+            ..Default::default()
+        };
+        assert!(prepare(html, &options).unwrap_err().contains("img_alt"));
+
+        let options = TemplateOptions {
+            minify: Some(true),
+            ..Default::default()
+        };
+        let prepared = prepare(html, &options).unwrap();
+        assert_eq!(prepared.html_parts, ["<div>\n<img src=\"", "\">\n</div>"]);
+    }
+
+    #[test]
+    fn test_prepare_invalid_html() {
+        let error = prepare("<div class=></div>{x}", &TemplateOptions::default());
+        assert!(error.unwrap_err().contains("invalid HTML syntax"));
+    }
+
+    fn prepare(
+        content: &str,
+        options: &TemplateOptions,
+    ) -> Result<crate::template::PreparedHtml, String> {
+        let (html_parts, _) = split_placeholders(content)?;
+        prepare_html(html_parts, options)
+    }
+}
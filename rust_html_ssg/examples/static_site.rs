@@ -0,0 +1,55 @@
+use rust_html::{rhtml, Template, TemplateGroup};
+use rust_html_ssg::Site;
+
+// Build with: cargo run -p rust_html_ssg --example static_site -- --out dist
+
+const POSTS: [(&str, &str); 2] = [
+    ("hello-world", "Hello, world!"),
+    ("static-sites", "Static sites with rust_html"),
+];
+
+fn layout(title: &str, content: Template) -> Template {
+    rhtml! { r#"
+        <!DOCTYPE html>
+        <html lang="en">
+            <head>
+                <title>{title}</title>
+            </head>
+            <body>
+                <nav><a href="/">Home</a> <a href="/blog/">Blog</a></nav>
+                <main>{content}</main>
+            </body>
+        </html>
+    "# }
+}
+
+fn home() -> Template {
+    layout("Home", rhtml! { "<h1>Welcome!</h1>" })
+}
+
+fn blog() -> Template {
+    let links: Vec<Template> = POSTS
+        .iter()
+        .map(|(slug, title)| rhtml! { r#"<li><a href="/blog/{slug}/">{title}</a></li>"# })
+        .collect();
+    let links = TemplateGroup(links);
+    layout("Blog", rhtml! { "<h1>Blog</h1><ul>{links}</ul>" })
+}
+
+fn post(title: &'static str) -> Template {
+    layout(title, rhtml! { "<h1>{title}</h1>" })
+}
+
+fn main() {
+    let mut site = Site::new()
+        .base_url("https://example.com")
+        .page("/", home)
+        .page("/blog/", blog)
+        .hidden_page("/404.html", || {
+            layout("Not found", rhtml! { "<h1>Not found</h1>" })
+        });
+    for (slug, title) in POSTS {
+        site = site.page(format!("/blog/{}/", slug), move || post(title));
+    }
+    site.run();
+}
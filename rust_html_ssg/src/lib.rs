@@ -0,0 +1,318 @@
+//! Static site generation for rust_html templates
+//!
+//! Register each route of the site with a function returning its
+//! `Template`, then build the site to an output directory:
+//!
+//! ```rust no_run
+//! use rust_html::{rhtml, Template};
+//! use rust_html_ssg::Site;
+//!
+//! fn home() -> Template {
+//!     rhtml! { "<h1>Welcome!</h1>" }
+//! }
+//!
+//! fn about() -> Template {
+//!     rhtml! { "<h1>About us</h1>" }
+//! }
+//!
+//! fn main() {
+//!     Site::new()
+//!         .base_url("https://example.com")
+//!         .page("/", home)
+//!         .page("/about", about)
+//!         .hidden_page("/404.html", || rhtml! { "<h1>Not found</h1>" })
+//!         .assets("static", "/static")
+//!         .run();
+//! }
+//! ```
+//!
+//! `Site::run` reads the output directory from the command line
+//! (`cargo run -- --out dist`), while `Site::build` can be called
+//! directly, e.g. from a `build.rs` script.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use rust_html::xml::Sitemap;
+use rust_html::Template;
+
+/// Output directory used by `Site::run` without `--out`
+pub const DEFAULT_OUTPUT_DIR: &str = "dist";
+
+/// Registry of pages and assets for a static site
+pub struct Site {
+    base_url: Option<String>,
+    pages: Vec<Page>,
+    assets: Vec<(PathBuf, String)>,
+}
+
+struct Page {
+    route: String,
+    render: Box<dyn Fn() -> Template>,
+    in_sitemap: bool,
+}
+
+/// Files written by `Site::build`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildSummary {
+    pub pages: Vec<PathBuf>,
+    pub assets: Vec<PathBuf>,
+    pub sitemap: Option<PathBuf>,
+}
+
+/// Static site generation error
+#[derive(Debug)]
+pub enum SsgError {
+    /// Route that can't be mapped to a file in the output directory
+    InvalidRoute(String),
+    /// Two routes writing to the same file
+    DuplicateRoute(String),
+    /// Failed to read or write a file
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl fmt::Display for SsgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SsgError::InvalidRoute(route) => write!(
+                f,
+                "invalid route '{}', routes must start with '/' and can't contain '.' or '..' segments",
+                route
+            ),
+            SsgError::DuplicateRoute(route) => {
+                write!(f, "route '{}' is registered more than once", route)
+            }
+            SsgError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for SsgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SsgError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Site {
+    fn default() -> Self {
+        Site::new()
+    }
+}
+
+impl Site {
+    pub fn new() -> Self {
+        Site {
+            base_url: None,
+            pages: vec![],
+            assets: vec![],
+        }
+    }
+
+    /// Public URL of the site, e.g. `https://example.com`.
+    /// A `sitemap.xml` is generated when this is set.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Adds a page to the site and its sitemap
+    ///
+    /// Routes without a file extension are written to `index.html`
+    /// in a directory of the same name, so `/about` is written to
+    /// `about/index.html`. Other routes, e.g. `/404.html`, are
+    /// written as is.
+    pub fn page<F>(self, route: impl Into<String>, render: F) -> Self
+    where
+        F: Fn() -> Template + 'static,
+    {
+        self.add_page(route.into(), Box::new(render), true)
+    }
+
+    /// Adds a page to the site that is left out of the sitemap,
+    /// e.g. error pages
+    pub fn hidden_page<F>(self, route: impl Into<String>, render: F) -> Self
+    where
+        F: Fn() -> Template + 'static,
+    {
+        self.add_page(route.into(), Box::new(render), false)
+    }
+
+    /// Copies all files in `source_dir` (recursively) to the site,
+    /// served under `route`, e.g. `.assets("static", "/static")`
+    pub fn assets(mut self, source_dir: impl Into<PathBuf>, route: impl Into<String>) -> Self {
+        self.assets.push((source_dir.into(), route.into()));
+        self
+    }
+
+    fn add_page(
+        mut self,
+        route: String,
+        render: Box<dyn Fn() -> Template>,
+        in_sitemap: bool,
+    ) -> Self {
+        self.pages.push(Page {
+            route,
+            render,
+            in_sitemap,
+        });
+        self
+    }
+
+    /// Renders all pages, copies the assets and writes the sitemap
+    /// to the output directory. Existing files are overwritten.
+    pub fn build(&self, output_dir: impl AsRef<Path>) -> Result<BuildSummary, SsgError> {
+        let output_dir = output_dir.as_ref();
+        let mut summary = BuildSummary::default();
+
+        // Validate all routes before writing anything
+        let mut page_paths = BTreeSet::new();
+        for page in self.pages.iter() {
+            let path = page_path(&page.route)?;
+            if !page_paths.insert(path) {
+                return Err(SsgError::DuplicateRoute(page.route.clone()));
+            }
+        }
+        for (_, route) in self.assets.iter() {
+            route_segments(route)?;
+        }
+
+        for page in self.pages.iter() {
+            let path = output_dir.join(page_path(&page.route)?);
+            let html: String = (page.render)().into();
+            write_file(&path, html.as_bytes())?;
+            summary.pages.push(path);
+        }
+
+        for (source_dir, route) in self.assets.iter() {
+            let mut target_dir = output_dir.to_path_buf();
+            target_dir.extend(route_segments(route)?);
+            copy_dir(source_dir, &target_dir, &mut summary.assets)?;
+        }
+
+        if let Some(base_url) = &self.base_url {
+            let path = output_dir.join("sitemap.xml");
+            write_file(&path, self.sitemap(base_url).as_bytes())?;
+            summary.sitemap = Some(path);
+        }
+        Ok(summary)
+    }
+
+    /// Builds the site to the directory given with `--out` (or `-o`)
+    /// on the command line, `dist` by default. Exits the process
+    /// with an error code if the build fails.
+    pub fn run(&self) {
+        let mut output_dir = PathBuf::from(DEFAULT_OUTPUT_DIR);
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--out" | "-o" => match args.next() {
+                    Some(dir) => output_dir = dir.into(),
+                    None => exit_with_error("missing directory after --out"),
+                },
+                "--help" | "-h" => {
+                    println!("Usage: [--out <DIR>]");
+                    println!("Builds the static site to <DIR> ({})", DEFAULT_OUTPUT_DIR);
+                    return;
+                }
+                arg => exit_with_error(&format!("unexpected argument '{}'", arg)),
+            }
+        }
+
+        match self.build(&output_dir) {
+            Ok(summary) => println!(
+                "Built {} pages and {} assets to {}",
+                summary.pages.len(),
+                summary.assets.len(),
+                output_dir.display()
+            ),
+            Err(err) => exit_with_error(&err.to_string()),
+        }
+    }
+
+    fn sitemap(&self, base_url: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
+        self.pages
+            .iter()
+            .filter(|page| page.in_sitemap)
+            .fold(Sitemap::new(), |sitemap, page| {
+                sitemap.url(format!("{}{}", base_url, page.route))
+            })
+            .render()
+            .into()
+    }
+}
+
+/// Relative path of the file a page route is written to
+fn page_path(route: &str) -> Result<PathBuf, SsgError> {
+    let segments = route_segments(route)?;
+    let is_file = !route.ends_with('/') && segments.last().is_some_and(|name| name.contains('.'));
+    let mut path: PathBuf = segments.into_iter().collect();
+    if !is_file {
+        path.push("index.html");
+    }
+    Ok(path)
+}
+
+/// Splits a route into path segments, rejecting routes
+/// that could be written outside the output directory
+fn route_segments(route: &str) -> Result<Vec<&str>, SsgError> {
+    let Some(relative) = route.strip_prefix('/') else {
+        return Err(SsgError::InvalidRoute(route.to_string()));
+    };
+    let relative = relative.strip_suffix('/').unwrap_or(relative);
+    if relative.is_empty() {
+        return Ok(vec![]);
+    }
+    let segments: Vec<_> = relative.split('/').collect();
+    let invalid = segments.iter().any(|segment| {
+        segment.is_empty() || *segment == "." || *segment == ".." || segment.contains('\\')
+    });
+    if invalid {
+        return Err(SsgError::InvalidRoute(route.to_string()));
+    }
+    Ok(segments)
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<(), SsgError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| io_error(parent, error))?;
+    }
+    std::fs::write(path, content).map_err(|error| io_error(path, error))
+}
+
+fn copy_dir(source: &Path, target: &Path, copied: &mut Vec<PathBuf>) -> Result<(), SsgError> {
+    let entries = std::fs::read_dir(source).map_err(|error| io_error(source, error))?;
+    std::fs::create_dir_all(target).map_err(|error| io_error(target, error))?;
+    for entry in entries {
+        let entry = entry.map_err(|error| io_error(source, error))?;
+        let source_path = entry.path();
+        let target_path = target.join(entry.file_name());
+        if source_path.is_dir() {
+            copy_dir(&source_path, &target_path, copied)?;
+        } else {
+            std::fs::copy(&source_path, &target_path)
+                .map_err(|error| io_error(&source_path, error))?;
+            copied.push(target_path);
+        }
+    }
+    Ok(())
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> SsgError {
+    SsgError::Io {
+        path: path.to_path_buf(),
+        error,
+    }
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    std::process::exit(1)
+}
@@ -0,0 +1,43 @@
+// Unit tests for the actix-web integration
+#[cfg(test)]
+mod test {
+    use actix_web::http::header;
+    use actix_web::{test, web, App};
+    use rust_html::*;
+
+    async fn page() -> Template {
+        let value = "<hello>";
+        rhtml! { "<div>{value}</div>" }
+    }
+
+    async fn list() -> TemplateGroup {
+        (1..=3).map(|i| rhtml! { "<li>{i}</li>" }).collect()
+    }
+
+    #[actix_web::test]
+    async fn test_template_responder() {
+        let app = test::init_service(App::new().route("/", web::get().to(page))).await;
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        let body = test::read_body(response).await;
+        assert_eq!(body, "<div>&lt;hello&gt;</div>");
+    }
+
+    #[actix_web::test]
+    async fn test_template_group_responder() {
+        let app = test::init_service(App::new().route("/", web::get().to(list))).await;
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        let body = test::read_body(response).await;
+        assert_eq!(body, "<li>1</li><li>2</li><li>3</li>");
+    }
+}
@@ -0,0 +1,68 @@
+// Unit tests for fingerprinted static assets
+#[cfg(test)]
+mod test {
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use axum::Router;
+    use http_body_util::BodyExt;
+    use rust_html::assets::asset_router;
+    use rust_html::*;
+    use tower::ServiceExt;
+
+    const APP_JS: Asset = asset!("static/app.js");
+    const STYLE_CSS: Asset = asset!("static/style.css");
+
+    #[test]
+    fn test_asset() {
+        assert_eq!(APP_JS.path, "static/app.js");
+        assert!(APP_JS.url.starts_with("/assets/app."));
+        assert!(APP_JS.url.ends_with(".js"));
+        assert_eq!(APP_JS.url.len(), "/assets/app.0123456789.js".len());
+        assert!(APP_JS.integrity.starts_with("sha384-"));
+        assert_eq!(APP_JS.contents, include_bytes!("../static/app.js"));
+        assert_ne!(APP_JS.url, STYLE_CSS.url);
+        assert_eq!(APP_JS.content_type(), "text/javascript; charset=utf-8");
+        assert_eq!(STYLE_CSS.content_type(), "text/css; charset=utf-8");
+    }
+
+    #[test]
+    fn test_asset_tags() {
+        let page = rhtml! { r#"<script src="{APP_JS}" integrity="{APP_JS.integrity}"></script>"# };
+        let expected = format!(
+            r#"<script src="{}" integrity="{}"></script>"#,
+            APP_JS.url.replace('/', "&#x2F;"),
+            APP_JS.integrity.replace('/', "&#x2F;"),
+        );
+        assert_eq!(String::from(page), expected);
+
+        let integrity = |asset: Asset| asset.integrity.replace('/', "&#x2F;");
+        let script = String::from(APP_JS.script());
+        assert!(script.contains(&format!(r#"integrity="{}""#, integrity(APP_JS))));
+        assert!(script.contains(r#"crossorigin="anonymous""#));
+        let stylesheet = String::from(STYLE_CSS.stylesheet());
+        assert!(stylesheet.starts_with(r#"<link rel="stylesheet""#));
+        assert!(stylesheet.contains(&format!(r#"integrity="{}""#, integrity(STYLE_CSS))));
+    }
+
+    #[tokio::test]
+    async fn test_asset_router() {
+        let app: Router = asset_router([APP_JS, STYLE_CSS, APP_JS]);
+        let request = Request::get(APP_JS.url).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "public, max-age=31536000, immutable"
+        );
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, APP_JS.contents);
+
+        let request = Request::get("/assets/app.js").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
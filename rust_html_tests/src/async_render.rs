@@ -0,0 +1,152 @@
+// Unit tests for async rendering
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+    use std::time::Duration;
+
+    async fn greeting(name: &str) -> String {
+        format!("hello, {}", name)
+    }
+
+    struct UserCard {
+        id: u32,
+    }
+
+    impl AsyncRender for UserCard {
+        async fn render_async(self) -> Template {
+            let name = greeting(&self.id.to_string()).await;
+            rhtml! { "<div>{name}</div>" }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_empty() {
+        test_eq(rhtml_async! {}.await, "");
+    }
+
+    #[tokio::test]
+    async fn test_async_fragment_context() {
+        let template = rhtml_async! { in "ol", "<li>{greeting(\"you\").await}</li>" }.await;
+        test_eq(template, "<li>hello, you</li>");
+    }
+
+    #[tokio::test]
+    async fn test_async_without_await() {
+        let value = 10;
+        test_eq(rhtml_async! { "<div>{value}</div>" }.await, "<div>10</div>");
+    }
+
+    #[tokio::test]
+    async fn test_async_future() {
+        test_eq(
+            rhtml_async! { r#"<span>{greeting("world").await}</span>"# }.await,
+            "<span>hello, world</span>",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_escaped() {
+        let sketchy = async { "<script>" };
+        test_eq(
+            rhtml_async! { "<span>{sketchy.await}</span>" }.await,
+            "<span>&lt;script&gt;</span>",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_url_attribute() {
+        let url = async { "javascript:alert(1)" };
+        test_eq(
+            rhtml_async! { r#"<svg><a href="{url.await}"></a></svg>"# }.await,
+            r#"<svg><a href="about:invalid"></a></svg>"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_template_future() {
+        let inner = async {
+            rhtml! { "<b>bold</b>" }
+        };
+        test_eq(
+            rhtml_async! { "<span>{inner.await}</span>" }.await,
+            "<span><b>bold</b></span>",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_render_trait() {
+        let card = UserCard { id: 1 };
+        test_eq(
+            rhtml_async! { "<main>{card.await}</main>" }.await,
+            "<main><div>hello, 1</div></main>",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_nested() {
+        let inner = rhtml_async! { r#"<i>{greeting("inner").await}</i>"# };
+        test_eq(
+            rhtml_async! { "<div>{inner.await}</div>" }.await,
+            "<div><i>hello, inner</i></div>",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_mixed_order() {
+        let first = "a";
+        let third = async { "c" };
+        test_eq(
+            rhtml_async! { r#"{first}-{async { "b" }.await}-{third.await}-{4}"# }.await,
+            "a-b-c-4",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_concurrent() {
+        // The first placeholder can only finish once the second one
+        // has run, so this only completes if both are polled concurrently
+        let (sender, receiver) = tokio::sync::oneshot::channel::<&str>();
+        let waiting = async { receiver.await.unwrap() };
+        let sending = async {
+            sender.send("world").unwrap();
+            "hello"
+        };
+        let template = rhtml_async! { "{waiting.await} {sending.await}" };
+        let result = tokio::time::timeout(Duration::from_secs(5), template)
+            .await
+            .expect("async placeholders were not rendered concurrently");
+        test_eq(result, "world hello");
+    }
+
+    #[tokio::test]
+    async fn test_stream_skips_empty_chunks() {
+        let (sender, receiver) = tokio::sync::oneshot::channel::<&str>();
+        let content = async move { receiver.await.unwrap() };
+        let mut stream = TemplateStream::from(rhtml_async! { "{content.await}" });
+
+        // Only empty parts are ready before the placeholder
+        let first = std::future::poll_fn(|cx| std::task::Poll::Ready(stream.poll_next_chunk(cx)));
+        assert!(first.await.is_pending());
+
+        sender.send("content").unwrap();
+        assert_eq!(stream.next_chunk().await.as_deref(), Some("content"));
+        assert_eq!(stream.next_chunk().await, None);
+    }
+
+    #[test]
+    fn test_async_send() {
+        fn assert_send<T: Send>(_: T) {}
+        let card = UserCard { id: 1 };
+        assert_send(async move { rhtml_async! { "{card.await}" }.await });
+    }
+
+    fn test_eq(template: Template, expected: &str) {
+        let template_string: String = template.into();
+        assert!(
+            template_string == expected,
+            "Macro test failed, expected:\n {}\nbut found:\n {}\n",
+            expected,
+            template_string
+        )
+    }
+}
@@ -0,0 +1,161 @@
+// Unit tests for the axum integration
+#[cfg(test)]
+mod test {
+    use axum::body::Body;
+    use axum::http::{header, StatusCode};
+    use axum::response::{IntoResponse, Response};
+    use http_body_util::BodyExt;
+    use rust_html::integration::HtmlResponse;
+    use rust_html::*;
+
+    #[tokio::test]
+    async fn test_template_response() {
+        let value = "<hello>";
+        let response = rhtml! { "<div>{value}</div>" }.into_response();
+        assert_html_content_type(&response);
+        assert_eq!(
+            body_string(response.into_body()).await,
+            "<div>&lt;hello&gt;</div>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_template_response() {
+        let inner = rhtml! { "<span>{10}</span>" };
+        let page = rhtml! { "<div>{inner}</div>" };
+        let response = TemplateStream::from(page).into_response();
+        assert_html_content_type(&response);
+        assert!(response.headers().get(header::CONTENT_LENGTH).is_none());
+        assert_eq!(
+            body_string(response.into_body()).await,
+            "<div><span>10</span></div>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_async_template_response() {
+        let title = async { "Title" };
+        let content = async {
+            rhtml! { "<p>content</p>" }
+        };
+        let response = rhtml_async! { "<h1>{title.await}</h1>{content.await}" }.into_response();
+        assert_html_content_type(&response);
+        assert_eq!(
+            body_string(response.into_body()).await,
+            "<h1>Title</h1><p>content</p>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_flushes_head_first() {
+        let (sender, receiver) = tokio::sync::oneshot::channel::<String>();
+        let content = async move { receiver.await.unwrap() };
+        let template = rhtml_async! { r#"
+            <!DOCTYPE html>
+            <html lang="en">
+                <head><title>Streaming</title></head>
+                <body>{content.await}</body>
+            </html>
+        "# };
+        let mut body = template.into_response().into_body();
+
+        // The head is sent before the content is available
+        let head = next_chunk(&mut body).await;
+        assert!(head.contains("<title>Streaming</title>"));
+        assert!(head.ends_with("<body>"));
+
+        sender.send("<content>".to_string()).unwrap();
+        let rest = body_string(body).await;
+        assert!(rest.starts_with("&lt;content&gt;</body>"));
+    }
+
+    #[tokio::test]
+    async fn test_with_status() {
+        let response = rhtml! { "<h1>Teapot</h1>" }
+            .with_status(StatusCode::IM_A_TEAPOT)
+            .into_response();
+        assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+        assert_html_content_type(&response);
+        assert_eq!(body_string(response.into_body()).await, "<h1>Teapot</h1>");
+    }
+
+    #[tokio::test]
+    async fn test_response_headers() {
+        let max_age = 3600;
+        let response = HtmlResponse::new(rhtml! { "<div></div>" })
+            .header("x-custom", "value")
+            .header(header::ETAG, "\"v1\"".to_string())
+            .cache_control(format!("public, max-age={}", max_age))
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_html_content_type(&response);
+        assert_eq!(response.headers().get("x-custom").unwrap(), "value");
+        assert_eq!(response.headers().get(header::ETAG).unwrap(), "\"v1\"");
+        assert_eq!(
+            response.headers().get(header::CACHE_CONTROL).unwrap(),
+            "public, max-age=3600"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_response_content_type_override() {
+        let response = HtmlResponse::new(rhtml! { "<div></div>" })
+            .header(header::CONTENT_TYPE, "application/xhtml+xml")
+            .into_response();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/xhtml+xml"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_response_invalid_header() {
+        let response = HtmlResponse::new(rhtml! { "<div></div>" })
+            .header("x-custom", "line\nbreak")
+            .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body_string(response.into_body()).await, "");
+    }
+
+    #[tokio::test]
+    async fn test_error_pages() {
+        let page = || rhtml! { "<h1>Error</h1>" };
+        let cases = [
+            (HtmlResponse::bad_request(page()), StatusCode::BAD_REQUEST),
+            (HtmlResponse::unauthorized(page()), StatusCode::UNAUTHORIZED),
+            (HtmlResponse::forbidden(page()), StatusCode::FORBIDDEN),
+            (HtmlResponse::not_found(page()), StatusCode::NOT_FOUND),
+            (
+                HtmlResponse::unprocessable_entity(page()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                HtmlResponse::internal_server_error(page()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+        for (html_response, status) in cases {
+            let response = html_response.into_response();
+            assert_eq!(response.status(), status);
+            assert_html_content_type(&response);
+            assert_eq!(body_string(response.into_body()).await, "<h1>Error</h1>");
+        }
+    }
+
+    fn assert_html_content_type(response: &Response) {
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+    }
+
+    async fn next_chunk(body: &mut Body) -> String {
+        let frame = body.frame().await.unwrap().unwrap();
+        String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap()
+    }
+
+    async fn body_string(body: Body) -> String {
+        let bytes = body.collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+}
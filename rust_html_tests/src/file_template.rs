@@ -0,0 +1,34 @@
+// Unit tests for templates loaded from files
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+
+    #[test]
+    fn test_file_template() {
+        let title = "Card";
+        let description = "<script>";
+        let template = rhtml_file!("templates/card.html");
+        assert_eq!(
+            String::from(template),
+            "<div class=\"card\">\n    <h2>Card</h2>\n    <p>{ &lt;script&gt; }</p>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn test_file_template_component() {
+        let title = rhtml! { "<b>Bold</b>" };
+        let description = 10;
+        let card = rhtml_file!("templates/card.html");
+        let page = rhtml! { "<main>{card}</main>" };
+        assert_eq!(
+            String::from(page),
+            "<main><div class=\"card\">\n    <h2><b>Bold</b></h2>\n    <p>{ 10 }</p>\n</div>\n</main>"
+        );
+    }
+
+    #[test]
+    fn test_livereload_script() {
+        let script = String::from(hot_reload::livereload_script());
+        assert!(script.contains(hot_reload::LIVERELOAD_PATH));
+    }
+}
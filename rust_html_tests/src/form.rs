@@ -0,0 +1,122 @@
+// Unit tests for forms generated with #[derive(Form)]
+#[cfg(test)]
+mod test {
+    use rust_html::form::{Form, FormErrors};
+    use rust_html::*;
+    use serde::Deserialize;
+
+    #[derive(Form, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Role {
+        Admin,
+        #[form(label = "Regular user")]
+        RegularUser,
+        #[form(skip)]
+        System,
+    }
+
+    #[derive(Form, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Account {
+        #[serde(skip)]
+        #[allow(dead_code)]
+        id: u64,
+        #[form(
+            label = "Full name",
+            placeholder = "Jane Doe",
+            minlength = 2,
+            maxlength = 50
+        )]
+        full_name: String,
+        #[form(input_type = "email")]
+        #[serde(rename = "mail")]
+        email: String,
+        #[form(min = 0, max = 150)]
+        age: Option<u32>,
+        #[serde(default)]
+        newsletter: bool,
+        role: Role,
+        team: Option<Role>,
+        #[form(input_type = "textarea", optional)]
+        bio: String,
+        #[form(input_type = "hidden")]
+        version: u32,
+    }
+
+    fn account() -> Account {
+        Account {
+            id: 7,
+            full_name: "Jane \"JD\" Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            age: None,
+            newsletter: true,
+            role: Role::RegularUser,
+            team: None,
+            bio: "<b>Hi</b>".to_string(),
+            version: 3,
+        }
+    }
+
+    #[test]
+    fn test_empty_form() {
+        let html = String::from(Account::empty_form());
+        assert!(!html.contains(r#"name="id""#));
+        assert!(html.contains(
+            r#"<div class="form-field"><label for="fullName">Full name</label><input id="fullName" name="fullName" type="text" value="" placeholder="Jane Doe" required minlength="2" maxlength="50"></div>"#
+        ));
+        assert!(html.contains(r#"<label for="mail">Email</label><input id="mail" name="mail" type="email" value="" required>"#));
+        assert!(html
+            .contains(r#"<input id="age" name="age" type="number" value="" min="0" max="150">"#));
+        assert!(html
+            .contains(r#"<input id="newsletter" name="newsletter" type="checkbox" value="true">"#));
+        assert!(html.contains(
+            r#"<select id="role" name="role" required><option value="admin">Admin</option><option value="regular_user">Regular user</option></select>"#
+        ));
+        assert!(html.contains(r#"<select id="team" name="team"><option value=""></option>"#));
+        assert!(html.contains("<textarea id=\"bio\" name=\"bio\">\n</textarea>"));
+        assert!(html.contains(r#"<input type="hidden" name="version" value="">"#));
+    }
+
+    #[test]
+    fn test_filled_form() {
+        let html = String::from(account().form());
+        assert!(html.contains(r#"value="Jane &quot;JD&quot; Doe""#));
+        assert!(html.contains(r#"type="checkbox" value="true" checked>"#));
+        assert!(html.contains(r#"<option value="regular_user" selected>Regular user</option>"#));
+        assert!(html
+            .contains("<textarea id=\"bio\" name=\"bio\">\n&lt;b&gt;Hi&lt;&#x2F;b&gt;</textarea>"));
+        assert!(html.contains(r#"<input type="hidden" name="version" value="3">"#));
+        assert!(!html.contains("form-errors"));
+        assert!(!html.contains("aria-invalid"));
+    }
+
+    #[test]
+    fn test_form_errors() {
+        let mut errors = FormErrors::new();
+        errors.add("mail", "Email is taken");
+        errors.add("mail", "Use <your> work email");
+        assert_eq!(errors.get("mail").len(), 2);
+        assert!(errors.get("fullName").is_empty());
+        let html = String::from(account().form_with_errors(&errors));
+        assert!(html.contains(
+            r#"<input id="mail" name="mail" type="email" value="jane@example.com" required aria-invalid="true" aria-describedby="mail-errors"><ul class="form-errors" id="mail-errors"><li>Email is taken</li><li>Use &lt;your&gt; work email</li></ul></div>"#
+        ));
+        assert_eq!(html.matches("form-errors").count(), 1);
+    }
+
+    #[test]
+    fn test_form_in_template() {
+        let fields = Account::empty_form();
+        let page = rhtml! { r#"<form method="post">{fields}<button>Save</button></form>"# };
+        let html = String::from(page);
+        assert!(html.starts_with(r#"<form method="post"><div class="form-field">"#));
+        assert!(html.ends_with("<button>Save</button></form>"));
+    }
+
+    #[test]
+    fn test_option_values_deserialize() {
+        // The option values are the names serde expects
+        let role: Role = serde_json::from_str(r#""regular_user""#).unwrap();
+        assert_eq!(role, Role::RegularUser);
+    }
+}
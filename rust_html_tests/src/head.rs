@@ -0,0 +1,80 @@
+// Unit tests for head dependencies of components
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+
+    fn counter(start: i32) -> Template {
+        rhtml! { r#"<my-counter value="{start}"></my-counter>"# }
+            .with_head(HeadItem::ModuleScript("/static/counter.js".to_string()))
+            .with_head(HeadItem::Style("my-counter { display: block; }"))
+    }
+
+    #[test]
+    fn test_head_items() {
+        let items = [
+            HeadItem::Stylesheet("/app.css".to_string()),
+            HeadItem::Script("/a.js?v=1&x=2".to_string()),
+            HeadItem::DeferredScript("//unpkg.com/alpinejs".to_string()),
+            HeadItem::ModuleScript("/b.js".to_string()),
+            HeadItem::Preload {
+                href: "/font.woff2".to_string(),
+                kind: "font".to_string(),
+            },
+            HeadItem::Preload {
+                href: "/hero.png".to_string(),
+                kind: "image".to_string(),
+            },
+            HeadItem::Style("p > a { color: red; }"),
+            HeadItem::InlineScript("if (a < b) {}"),
+            HeadItem::Script("javascript:alert(1)".to_string()),
+        ];
+        let html: Vec<String> = items
+            .into_iter()
+            .map(|item| rhtml! { "{item}" }.into())
+            .collect();
+        assert_eq!(
+            html,
+            [
+                r#"<link rel="stylesheet" href="&#x2F;app.css">"#,
+                r#"<script src="&#x2F;a.js?v=1&amp;x=2"></script>"#,
+                r#"<script src="&#x2F;&#x2F;unpkg.com&#x2F;alpinejs" defer></script>"#,
+                r#"<script type="module" src="&#x2F;b.js"></script>"#,
+                r#"<link rel="preload" href="&#x2F;font.woff2" as="font" crossorigin>"#,
+                r#"<link rel="preload" href="&#x2F;hero.png" as="image">"#,
+                "<style>p > a { color: red; }</style>",
+                "<script>if (a < b) {}</script>",
+                r#"<script src="about:invalid"></script>"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_head() {
+        let counters: TemplateGroup = (0..3).map(counter).collect();
+        let content = rhtml! { "<main>{counters}</main>" }
+            .with_head(HeadItem::Stylesheet("/app.css".to_string()));
+        assert_eq!(
+            String::from(content.head()),
+            concat!(
+                r#"<link rel="stylesheet" href="&#x2F;app.css">"#,
+                r#"<script type="module" src="&#x2F;static&#x2F;counter.js"></script>"#,
+                "<style>my-counter { display: block; }</style>"
+            )
+        );
+        assert_eq!(
+            String::from(content),
+            r#"<main><my-counter value="0"></my-counter><my-counter value="1"></my-counter><my-counter value="2"></my-counter></main>"#
+        );
+    }
+
+    #[test]
+    fn test_head_with_scoped_css() {
+        let note = rhtml! { "<style scoped>p {{ color: gray; }}</style><p>Note</p>" }
+            .with_head(HeadItem::Stylesheet("/app.css".to_string()));
+        let head = String::from(note.head());
+        assert!(
+            head.starts_with(r#"<link rel="stylesheet" href="&#x2F;app.css"><style>p[data-rhtml-"#)
+        );
+        assert_eq!(String::from(rhtml! { "<p>Text</p>" }.head()), "");
+    }
+}
@@ -0,0 +1,252 @@
+// Unit tests for the htmx integration
+#[cfg(test)]
+mod test {
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+    use http_body_util::BodyExt;
+    use rust_html::htmx::{HxRequest, HxResponse, HxSwap, OobResponse};
+    use rust_html::integration::HtmlResponse;
+    use rust_html::*;
+    use tower::ServiceExt;
+
+    fn layout(content: Template) -> Template {
+        rhtml! { "<main>{content}</main>" }
+    }
+
+    async fn contacts(hx: HxRequest) -> Template {
+        hx.fragment_or_page(rhtml! { "<p>contacts</p>" }, layout)
+    }
+
+    async fn echo(hx: HxRequest) -> String {
+        format!("{:?}", hx)
+    }
+
+    async fn get_body(request: Request<Body>) -> String {
+        let app = Router::new()
+            .route("/", get(contacts))
+            .route("/echo", get(echo));
+        let response = app.oneshot(request).await.unwrap();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_regular_request_gets_page() {
+        let request = Request::get("/").body(Body::empty()).unwrap();
+        assert_eq!(get_body(request).await, "<main><p>contacts</p></main>");
+    }
+
+    #[tokio::test]
+    async fn test_htmx_request_gets_fragment() {
+        let request = Request::get("/")
+            .header("HX-Request", "true")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(get_body(request).await, "<p>contacts</p>");
+    }
+
+    #[tokio::test]
+    async fn test_boosted_request_gets_page() {
+        let request = Request::get("/")
+            .header("HX-Request", "true")
+            .header("HX-Boosted", "true")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(get_body(request).await, "<main><p>contacts</p></main>");
+    }
+
+    #[tokio::test]
+    async fn test_extract_headers() {
+        let request = Request::get("/echo")
+            .header("HX-Request", "true")
+            .header("HX-Target", "contacts")
+            .header("HX-Trigger", "load-button")
+            .header("HX-Trigger-Name", "load")
+            .header("HX-Current-URL", "http://localhost/")
+            .body(Body::empty())
+            .unwrap();
+        let expected = HxRequest {
+            request: true,
+            boosted: false,
+            target: Some("contacts".to_string()),
+            trigger: Some("load-button".to_string()),
+            trigger_name: Some("load".to_string()),
+            current_url: Some("http://localhost/".to_string()),
+        };
+        assert_eq!(get_body(request).await, format!("{:?}", expected));
+    }
+
+    #[test]
+    fn test_response_headers() {
+        let response = HxResponse::new(rhtml! { "<p>saved</p>" })
+            .redirect("/contacts")
+            .retarget("#list")
+            .reswap(HxSwap::OuterHtml)
+            .trigger("saved")
+            .trigger("refresh")
+            .into_response();
+        let headers = response.headers();
+        assert_eq!(headers.get("HX-Redirect").unwrap(), "/contacts");
+        assert_eq!(headers.get("HX-Retarget").unwrap(), "#list");
+        assert_eq!(headers.get("HX-Reswap").unwrap(), "outerHTML");
+        assert_eq!(headers.get("HX-Trigger").unwrap(), "saved, refresh");
+        assert_eq!(
+            headers.get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn test_response_from_html_response() {
+        let response: HxResponse =
+            HtmlResponse::unprocessable_entity(rhtml! { "<p>error</p>" }).into();
+        let response = response.reswap("innerHTML swap:1s").into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.headers().get("HX-Reswap").unwrap(),
+            "innerHTML swap:1s"
+        );
+        assert!(response.headers().get("HX-Trigger").is_none());
+    }
+
+    #[test]
+    fn test_oob_fragments() {
+        let count = 3;
+        let template = OobResponse::new(rhtml! { "<li>Bob</li>" })
+            .fragment(
+                "count",
+                HxSwap::InnerHtml,
+                rhtml! { "<span>{count}</span>" },
+            )
+            .fragment(
+                "flash",
+                HxSwap::OuterHtml,
+                rhtml! { r#"<p class="flash">Added <b>Bob</b></p>"# },
+            )
+            .fragment(
+                "avatar",
+                HxSwap::BeforeEnd,
+                rhtml! { "<img src='bob.png' alt='Bob'>" },
+            )
+            .render()
+            .unwrap();
+        assert_eq!(
+            String::from(template),
+            concat!(
+                "<li>Bob</li>",
+                r#"<span id="count" hx-swap-oob="innerHTML">3</span>"#,
+                r#"<p id="flash" hx-swap-oob="outerHTML" class="flash">Added <b>Bob</b></p>"#,
+                r#"<img id="avatar" hx-swap-oob="beforeend" src='bob.png' alt='Bob'>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_oob_nested_same_element() {
+        let template = OobResponse::new(rhtml! {})
+            .fragment(
+                "list",
+                HxSwap::OuterHtml,
+                rhtml! { "<div><div>a</div><div>b</div></div>" },
+            )
+            .render()
+            .unwrap();
+        assert_eq!(
+            String::from(template),
+            r#"<div id="list" hx-swap-oob="outerHTML"><div>a</div><div>b</div></div>"#
+        );
+    }
+
+    #[test]
+    fn test_oob_comments_and_scripts() {
+        let template = OobResponse::new(rhtml! {})
+            .fragment(
+                "a",
+                HxSwap::OuterHtml,
+                rhtml! { "<div><!-- <div> --></div>" },
+            )
+            .fragment(
+                "b",
+                HxSwap::OuterHtml,
+                rhtml! { "<div><script>let end = '</div>';</script></div>" },
+            )
+            .render()
+            .unwrap();
+        assert_eq!(
+            String::from(template),
+            concat!(
+                r#"<div id="a" hx-swap-oob="outerHTML"><!-- <div> --></div>"#,
+                r#"<div id="b" hx-swap-oob="outerHTML"><script>let end = '</div>';</script></div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_oob_invalid_fragments() {
+        let invalid = [
+            rhtml! { "text only" },
+            rhtml! { "<div>a</div><div>b</div>" },
+            rhtml! { "<div>a</div> trailing text" },
+            rhtml! { "<br><br>" },
+            rhtml! { r#"<div id="other"></div>"# },
+            rhtml! { r#"<div hx-swap-oob="true"></div>"# },
+        ];
+        for fragment in invalid {
+            let html = String::from(fragment.clone());
+            let result = OobResponse::new(rhtml! {})
+                .fragment("target", HxSwap::OuterHtml, fragment)
+                .render();
+            assert!(result.is_err(), "Expected invalid fragment: {}", html);
+        }
+        let result = OobResponse::new(rhtml! {})
+            .fragment("bad id", HxSwap::OuterHtml, rhtml! { "<div></div>" })
+            .render();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_oob_attribute_values_are_skipped() {
+        let template = OobResponse::new(rhtml! {})
+            .fragment(
+                "target",
+                HxSwap::OuterHtml,
+                rhtml! { r#"<div title="id=1 >"></div>"# },
+            )
+            .render()
+            .unwrap();
+        assert_eq!(
+            String::from(template),
+            r#"<div id="target" hx-swap-oob="outerHTML" title="id=1 >"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_oob_response() {
+        let response = OobResponse::new(rhtml! { "<li>Bob</li>" })
+            .fragment("count", HxSwap::InnerHtml, rhtml! { "<span>1</span>" })
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = OobResponse::new(rhtml! {})
+            .fragment("count", HxSwap::InnerHtml, rhtml! { "1" })
+            .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let response: HxResponse = OobResponse::new(rhtml! { "<li>Bob</li>" }).into();
+        let response = response.trigger("added").into_response();
+        assert_eq!(response.headers().get("HX-Trigger").unwrap(), "added");
+    }
+
+    #[tokio::test]
+    async fn test_oob_error_is_not_sent() {
+        let invalid =
+            || OobResponse::new(rhtml! {}).fragment("count", HxSwap::InnerHtml, rhtml! { "1" });
+        let response: HxResponse = invalid().into();
+        for response in [invalid().into_response(), response.into_response()] {
+            assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            assert!(bytes.is_empty());
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// Unit tests for the hyper/http integration
+#[cfg(test)]
+mod test {
+    use bytes::Bytes;
+    use http::{header, Response, StatusCode};
+    use http_body_util::{BodyExt, Full};
+    use rust_html::*;
+
+    fn page() -> Template {
+        let value = "<hello>";
+        rhtml! { "<div>{value}</div>" }
+    }
+
+    #[tokio::test]
+    async fn test_full_body_response() {
+        let response: Response<Full<Bytes>> = page().into();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "<div>&lt;hello&gt;</div>");
+    }
+
+    #[test]
+    fn test_string_body_response() {
+        let response: Response<String> = page().into();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(response.body(), "<div>&lt;hello&gt;</div>");
+    }
+}
@@ -0,0 +1,54 @@
+// Unit tests for id checks
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+
+    #[test]
+    fn test_static_ids() {
+        let page = rhtml! { r##"<a href="#main">Skip</a><main id="main"><h1 id="title">Title</h1><section aria-labelledby="title"></section></main>"## };
+        page.check_ids();
+        let allowed = rhtml! { allow(dangling_ids), r##"<a href="#main">Skip to content</a>"## };
+        assert_eq!(
+            String::from(allowed),
+            r##"<a href="#main">Skip to content</a>"##
+        );
+    }
+
+    #[test]
+    fn test_composed_ids() {
+        let field = |id: &str| rhtml! { r#"<label for="{id}">Name</label><input id="{id}">"# };
+        let form = rhtml! { "<form>{field(\"first\")}{field(\"last\")}</form>" };
+        form.check_ids();
+
+        let nav = rhtml! { allow(ids), r##"<a href="#content">Skip</a>"## };
+        let page = rhtml! { r#"<body>{nav}<main id="content"></main></body>"# };
+        page.check_ids();
+
+        let href = "#a&b";
+        let id = "a&b";
+        let escaped = rhtml! { r#"<a href="{href}">A</a><div id="{id}"></div>"# };
+        escaped.check_ids();
+    }
+
+    #[test]
+    #[should_panic(expected = "id 'name' is used more than once")]
+    fn test_duplicate_ids() {
+        let field = rhtml! { r#"<label>Name <input id="name"></label>"# };
+        let form = rhtml! { "<form>{field}{field}</form>" };
+        form.check_ids();
+    }
+
+    #[test]
+    #[should_panic(expected = "for on '<label>' refers to id 'email'")]
+    fn test_dangling_reference() {
+        let input = rhtml! { allow(input_label), r#"<input id="e-mail">"# };
+        let form = rhtml! { r#"<form><label for="email">Email</label>{input}</form>"# };
+        form.check_ids();
+    }
+
+    #[test]
+    fn test_raw_text_and_comments() {
+        let page = rhtml! { r#"<!-- <div id="a"> --><script>let html = '<div id="a">';</script><div id="a"></div>"# };
+        page.check_ids();
+    }
+}
@@ -0,0 +1,105 @@
+// Unit tests for JSON data in templates
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    #[derive(Serialize)]
+    struct User {
+        name: String,
+        admin: bool,
+    }
+
+    #[test]
+    fn test_json_script() {
+        let user = User {
+            name: "</script><!-- & \u{2028}".to_string(),
+            admin: false,
+        };
+        let page = rhtml! { r#"<script id="user" type="application/json">{Json(&user)}</script>"# };
+        assert_eq!(
+            String::from(page),
+            r#"<script id="user" type="application/json">{"name":"\u003c/script\u003e\u003c!-- \u0026 \u2028","admin":false}</script>"#
+        );
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let user = User {
+            name: "<b>Bob</b> & \"friends\"".to_string(),
+            admin: true,
+        };
+        let json = String::from(Json(&user).render());
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["name"], "<b>Bob</b> & \"friends\"");
+        assert_eq!(value["admin"], true);
+    }
+
+    #[test]
+    fn test_json_attribute() {
+        let state = User {
+            name: "Bob's \"cat\"".to_string(),
+            admin: false,
+        };
+        let json = Json(&state);
+        let div = rhtml! { r#"<div data-state="{json.attribute()}"></div>"# };
+        assert_eq!(
+            String::from(div),
+            r#"<div data-state="{&quot;name&quot;:&quot;Bob&#x27;s \&quot;cat\&quot;&quot;,&quot;admin&quot;:false}"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_json_in_attribute() {
+        let value = User {
+            name: "\"><script>alert(1)</script>".to_string(),
+            admin: false,
+        };
+        let div = rhtml! { r#"<div data-x="{Json(&value)}"></div>"# };
+        assert_eq!(
+            String::from(div),
+            r#"<div data-x="{&quot;name&quot;:&quot;\&quot;&gt;&lt;script&gt;alert(1)&lt;&#x2F;script&gt;&quot;,&quot;admin&quot;:false}"></div>"#
+        );
+        let div = rhtml! { r#"<div data-x='{Json("it's")}' title="a {Json(1)} b"></div>"# };
+        assert_eq!(
+            String::from(div),
+            r#"<div data-x='&quot;it&#x27;s&quot;' title="a 1 b"></div>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_awaited_json_in_attribute() {
+        async fn load() -> Json<User> {
+            Json(User {
+                name: "\"><b>".to_string(),
+                admin: true,
+            })
+        }
+        let div = rhtml_async! { r#"<div data-state="{load().await}"></div>"# }.await;
+        assert_eq!(
+            String::from(div),
+            r#"<div data-state="{&quot;name&quot;:&quot;\&quot;&gt;&lt;b&gt;&quot;,&quot;admin&quot;:true}"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_json_error() {
+        let map = HashMap::from([((1, 2), "tuple keys")]);
+        assert_eq!(String::from(Json(&map).render()), "null");
+    }
+
+    #[test]
+    fn test_json_ld() {
+        let data = serde_json::json!({
+            "@context": "https://schema.org",
+            "@type": "Article",
+            "headline": "Hello </script>",
+        });
+        let script = String::from(json_ld(&data));
+        assert_eq!(
+            script,
+            r#"<script type="application/ld+json">{"@context":"https://schema.org","@type":"Article","headline":"Hello \u003c/script\u003e"}</script>"#
+        );
+    }
+}
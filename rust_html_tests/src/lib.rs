@@ -1,3 +1,24 @@
+mod actix_web_integration;
+mod assets;
+mod async_render;
+mod axum_integration;
+mod file_template;
+mod form;
+mod head;
+mod htmx_integration;
+mod hyper_integration;
+mod ids;
+mod json;
+mod poem_integration;
+mod rocket_integration;
+mod scoped_css;
+mod sse_integration;
+mod static_site;
+mod svg;
+mod warp_integration;
+mod websocket_integration;
+mod xml_templates;
+
 // Unit tests for RHTML crate
 #[cfg(test)]
 mod test {
@@ -324,7 +345,7 @@ mod test {
     #[test]
     pub fn test_html_root() {
         // Just a compile ok check
-        rhtml! {r#"
+        rhtml! { allow(html_lang), r#"
             <!DOCTYPE html>
             <html>
                 <head></head>
@@ -340,6 +361,131 @@ mod test {
         test_eq(rhtml! {"<th>{10}</th>"}, "<th>10</th>");
     }
 
+    #[test]
+    pub fn test_fragment_context() {
+        let value = 1;
+        test_eq(rhtml! { in "ul", "<li>{value}</li>" }, "<li>1</li>");
+        test_eq(
+            rhtml! { in "select", r#"<option value="{value}">One</option>"# },
+            r#"<option value="1">One</option>"#,
+        );
+        test_eq(
+            rhtml! { in "colgroup", r#"<col span="2">"# },
+            r#"<col span="2">"#,
+        );
+        test_eq(
+            rhtml! { in "tbody", "<tr><td>{value}</td></tr>", },
+            "<tr><td>1</td></tr>",
+        );
+    }
+
+    #[test]
+    pub fn test_allow_fixups() {
+        test_eq(
+            rhtml! { allow(fixups), "<ul><li>One<li>Two</ul>" },
+            "<ul><li>One<li>Two</ul>",
+        );
+        test_eq(
+            rhtml! { allow(fixups), "<table><tr><td>1</td></tr></table>" },
+            "<table><tr><td>1</td></tr></table>",
+        );
+    }
+
+    #[test]
+    pub fn test_placeholder_positions() {
+        let rows = TemplateGroup(vec![rhtml! { in "tbody", "<tr><td>1</td></tr>" }]);
+        test_eq(
+            rhtml! { "<table><tbody>{rows}</tbody></table>" },
+            "<table><tbody><tr><td>1</td></tr></tbody></table>",
+        );
+        let attributes = Unescaped("hidden".to_string());
+        test_eq(
+            rhtml! { r#"<div class="a {1} b" {attributes}></div>"# },
+            r#"<div class="a 1 b" hidden></div>"#,
+        );
+    }
+
+    #[test]
+    pub fn test_lint_levels() {
+        test_eq(
+            rhtml! { allow(img_alt), r#"<img src="a.png">"# },
+            r#"<img src="a.png">"#,
+        );
+        test_eq(
+            rhtml! { allow(a11y), "<h1>Title</h1><h3>Skipped</h3>" },
+            "<h1>Title</h1><h3>Skipped</h3>",
+        );
+        test_eq(
+            rhtml! { deny(a11y), r#"<label>Name <input name="name"></label>"# },
+            r#"<label>Name <input name="name"></label>"#,
+        );
+    }
+
+    #[test]
+    pub fn test_unknown_names() {
+        let symbol = "+";
+        test_eq(
+            rhtml! { deny(unknown_names), prefixes("@", "x-"), r#"<div x-data="calculator"><button class="add" @click="insert('{symbol}')">{symbol}</button><my-display value="0"></my-display></div>"# },
+            r#"<div x-data="calculator"><button class="add" @click="insert('+')">+</button><my-display value="0"></my-display></div>"#,
+        );
+    }
+
+    #[test]
+    pub fn test_crate_config() {
+        // Prefixes and lint levels from [package.metadata.rust_html]
+        test_eq(
+            rhtml! { r#"<div hx-get="/items" sse-connect="/events"></div>"# },
+            r#"<div hx-get="/items" sse-connect="/events"></div>"#,
+        );
+        test_eq(
+            rhtml! { allow(unknown_names), r#"<div hreff="/"></div>"# },
+            r#"<div hreff="/"></div>"#,
+        );
+    }
+
+    #[test]
+    pub fn test_minify() {
+        let name = "World";
+        let page = rhtml! { minify(true), r#"
+            <div class="a  b">
+                <p>Hello,   {name}!</p>
+                <pre>  keep
+    this</pre>
+            </div>
+        "# };
+        test_eq(
+            page,
+            "\n<div class=\"a  b\">\n<p>Hello, World!</p>\n<pre>  keep\n    this</pre>\n</div>\n",
+        );
+    }
+
+    #[test]
+    pub fn test_practice_lints() {
+        test_eq(
+            rhtml! { deny(practices), r#"<a href="https://example.com" target="_blank" rel="noopener">Example</a>"# },
+            r#"<a href="https://example.com" target="_blank" rel="noopener">Example</a>"#,
+        );
+        test_eq(
+            rhtml! { allow(practices, unknown_names), r#"<center onclick="go()">Old</center>"# },
+            r#"<center onclick="go()">Old</center>"#,
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    pub fn test_lint_warnings() {
+        // Warnings are reported as use of deprecated constants
+        let label = "Save";
+        test_eq(
+            rhtml! { "<button>{label}</button><button></button>" },
+            "<button>Save</button><button></button>",
+        );
+        test_eq(
+            rhtml! { r#"<a href="/" target="_blank">Home</a>"# },
+            r#"<a href="/" target="_blank">Home</a>"#,
+        );
+    }
+
     fn test_eq(template: Template, expected: &str) {
         let template_string: String = template.into();
         assert!(
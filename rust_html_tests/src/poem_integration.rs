@@ -0,0 +1,22 @@
+// Unit tests for the poem integration
+#[cfg(test)]
+mod test {
+    use poem::test::TestClient;
+    use poem::{get, handler, Route};
+    use rust_html::*;
+
+    #[handler]
+    fn page() -> Template {
+        let value = "<hello>";
+        rhtml! { "<div>{value}</div>" }
+    }
+
+    #[tokio::test]
+    async fn test_template_response() {
+        let client = TestClient::new(Route::new().at("/", get(page)));
+        let response = client.get("/").send().await;
+        response.assert_status_is_ok();
+        response.assert_content_type("text/html; charset=utf-8");
+        response.assert_text("<div>&lt;hello&gt;</div>").await;
+    }
+}
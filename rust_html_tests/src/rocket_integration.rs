@@ -0,0 +1,23 @@
+// Unit tests for the rocket integration
+#[cfg(test)]
+mod test {
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket::{get, routes};
+    use rust_html::*;
+
+    #[get("/")]
+    fn page() -> Template {
+        let value = "<hello>";
+        rhtml! { "<div>{value}</div>" }
+    }
+
+    #[test]
+    fn test_template_responder() {
+        let client = Client::tracked(rocket::build().mount("/", routes![page])).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.into_string().unwrap(), "<div>&lt;hello&gt;</div>");
+    }
+}
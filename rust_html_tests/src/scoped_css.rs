@@ -0,0 +1,78 @@
+// Unit tests for scoped component styles
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+
+    struct Card<'a> {
+        title: &'a str,
+    }
+
+    impl Render for Card<'_> {
+        fn render(&self) -> Template {
+            let title = self.title;
+            rhtml! { r#"<style scoped>
+                .card > h2 {{ color: teal; }}
+                @media (max-width: 600px) {{ .card {{ padding: 0; }} }}
+            </style><div class="card"><h2>{title}</h2></div>"# }
+        }
+    }
+
+    #[test]
+    fn test_scoped_style() {
+        let card = Card { title: "Hello" }.render();
+        let html = String::from(card.clone());
+        assert!(!html.contains("<style"));
+        let attribute = html.split_whitespace().nth(1).unwrap().to_string();
+        assert!(attribute.starts_with("data-rhtml-"));
+        assert_eq!(
+            html,
+            format!(
+                r#"<div {a} class="card"><h2 {a}>Hello</h2></div>"#,
+                a = attribute
+            )
+        );
+        assert_eq!(
+            String::from(card.scoped_css()),
+            format!(
+                ".card > h2[{a}] {{ color: teal;}}\n@media (max-width: 600px) {{.card[{a}] {{ padding: 0;}}\n}}\n",
+                a = attribute
+            )
+        );
+    }
+
+    #[test]
+    fn test_collect_scoped_css() {
+        let badge =
+            rhtml! { r#"<style scoped>span {{ font-weight: bold; }}</style><span>New</span>"# };
+        let cards: TemplateGroup = ["A", "B"]
+            .iter()
+            .map(|title| Card { title }.render())
+            .collect();
+        let content = rhtml! { "<main>{cards}{badge}</main>" };
+        let css = String::from(content.scoped_css());
+        assert_eq!(css.matches("color: teal").count(), 1);
+        assert!(css.ends_with("] { font-weight: bold;}\n"));
+
+        let page = rhtml! { r#"<!DOCTYPE html><html lang="en"><head><style>{content.scoped_css()}</style></head><body>{content}</body></html>"# };
+        let html = String::from(page);
+        assert!(html.contains("<style>.card > h2[data-rhtml-"));
+        assert_eq!(html.matches("<div data-rhtml-").count(), 2);
+    }
+
+    #[test]
+    fn test_unscoped_style() {
+        let page = rhtml! { "<style>p {{ margin: 0; }}</style><p>Text</p>" };
+        assert_eq!(page.scoped_css(), Template::from(""));
+        assert_eq!(
+            String::from(page),
+            "<style>p { margin: 0; }</style><p>Text</p>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_scoped_style() {
+        let page = rhtml_async! { r#"<style scoped>p {{ margin: 0; }}</style><p>{async { "Text" }.await}</p>"# }.await;
+        assert!(String::from(page.scoped_css()).starts_with("p[data-rhtml-"));
+        assert!(String::from(page).starts_with("<p data-rhtml-"));
+    }
+}
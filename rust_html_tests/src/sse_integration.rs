@@ -0,0 +1,60 @@
+// Unit tests for the server-sent events integration
+#[cfg(test)]
+mod test {
+    use axum::http::header;
+    use axum::response::sse::Event;
+    use axum::response::IntoResponse;
+    use futures_util::stream;
+    use http_body_util::BodyExt;
+    use rust_html::integration::template_sse;
+    use rust_html::*;
+
+    async fn sse_body(templates: Vec<Template>) -> String {
+        let response = template_sse(stream::iter(templates)).into_response();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_single_line_events() {
+        let templates = (1..=2).map(|i| rhtml! { "<span>{i}</span>" }).collect();
+        assert_eq!(
+            sse_body(templates).await,
+            "data: <span>1</span>\n\ndata: <span>2</span>\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multi_line_event() {
+        let value = "<escaped>";
+        let template = rhtml! { "<ul>\n    <li>{value}</li>\n</ul>" };
+        assert_eq!(
+            sse_body(vec![template]).await,
+            "data: <ul>\ndata:     <li>&lt;escaped&gt;</li>\ndata: </ul>\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_carriage_returns() {
+        let windows = Unescaped("<p>\r\none\rtwo</p>".to_string());
+        let template = rhtml! { "{windows}" };
+        assert_eq!(
+            sse_body(vec![template]).await,
+            "data: <p>\ndata: one\ndata: two</p>\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_named_event() {
+        let event = Event::from(rhtml! { "<b>hi</b>" }).event("update");
+        let response =
+            axum::response::sse::Sse::new(stream::iter([Ok::<_, std::convert::Infallible>(event)]))
+                .into_response();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(bytes, "data: <b>hi</b>\nevent: update\n\n");
+    }
+}
@@ -0,0 +1,103 @@
+// Unit tests for static site generation
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+    use rust_html_ssg::{Site, SsgError};
+    use std::path::{Path, PathBuf};
+
+    fn output_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rust_html_ssg_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn read(path: &Path) -> String {
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_build_pages() {
+        let dir = output_dir("pages");
+        let summary = Site::new()
+            .page("/", || rhtml! { "<h1>Home</h1>" })
+            .page("/about", || rhtml! { "<h1>About</h1>" })
+            .page("/blog/post/", || rhtml! { "<h1>Post</h1>" })
+            .hidden_page("/404.html", || rhtml! { "<h1>Not found</h1>" })
+            .build(&dir)
+            .unwrap();
+
+        assert_eq!(summary.pages.len(), 4);
+        assert_eq!(summary.sitemap, None);
+        assert_eq!(read(&dir.join("index.html")), "<h1>Home</h1>");
+        assert_eq!(read(&dir.join("about/index.html")), "<h1>About</h1>");
+        assert_eq!(read(&dir.join("blog/post/index.html")), "<h1>Post</h1>");
+        assert_eq!(read(&dir.join("404.html")), "<h1>Not found</h1>");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_sitemap() {
+        let dir = output_dir("sitemap");
+        let summary = Site::new()
+            .base_url("https://example.com/")
+            .page("/", || rhtml! { "home" })
+            .page("/tom&jerry/", || rhtml! { "tom &amp; jerry" })
+            .hidden_page("/404.html", || rhtml! { "not found" })
+            .build(&dir)
+            .unwrap();
+
+        let sitemap = read(&summary.sitemap.unwrap());
+        assert!(sitemap.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(sitemap.contains("<loc>https://example.com/</loc>"));
+        assert!(sitemap.contains("<loc>https://example.com/tom&amp;jerry/</loc>"));
+        assert!(!sitemap.contains("404"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_assets() {
+        let dir = output_dir("assets");
+        let assets = output_dir("assets_source");
+        std::fs::create_dir_all(assets.join("css")).unwrap();
+        std::fs::write(assets.join("app.js"), "console.log(1);").unwrap();
+        std::fs::write(assets.join("css/site.css"), "body {}").unwrap();
+
+        let summary = Site::new()
+            .assets(&assets, "/static/")
+            .assets(&assets, "/")
+            .build(&dir)
+            .unwrap();
+
+        assert_eq!(summary.assets.len(), 4);
+        assert_eq!(read(&dir.join("static/app.js")), "console.log(1);");
+        assert_eq!(read(&dir.join("static/css/site.css")), "body {}");
+        assert_eq!(read(&dir.join("css/site.css")), "body {}");
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_dir_all(&assets).unwrap();
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let dir = output_dir("errors");
+        let page = || rhtml! { "" };
+        for route in ["about", "/../secret", "/a//b", "/./a"] {
+            let result = Site::new().page(route, page).build(&dir);
+            assert!(
+                matches!(result, Err(SsgError::InvalidRoute(_))),
+                "{}",
+                route
+            );
+        }
+
+        let result = Site::new()
+            .page("/about", page)
+            .page("/about/", page)
+            .build(&dir);
+        assert!(matches!(result, Err(SsgError::DuplicateRoute(_))));
+
+        let result = Site::new().assets(dir.join("missing"), "/").build(&dir);
+        assert!(matches!(result, Err(SsgError::Io { .. })));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
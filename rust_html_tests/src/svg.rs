@@ -0,0 +1,109 @@
+// Unit tests for SVG and MathML templates
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+
+    #[test]
+    fn test_inline_svg() {
+        let size = 24;
+        let icon = rhtml! { r#"<svg viewBox="0 0 {size} {size}"><path d="M0 0h24v24H0z"/></svg>"# };
+        assert_eq!(
+            String::from(icon),
+            r#"<svg viewBox="0 0 24 24"><path d="M0 0h24v24H0z"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_svg_fragment() {
+        let d = "M0 0L10 10";
+        let path = rhtml! { r#"<path d="{d}"/>"# };
+        let icon = rhtml! { "<svg>{path}</svg>" };
+        assert_eq!(String::from(icon), r#"<svg><path d="M0 0L10 10"/></svg>"#);
+    }
+
+    #[test]
+    fn test_mathml_fragment() {
+        let x = 2;
+        let math = rhtml! { "<mi>x</mi><mo>=</mo><mn>{x}</mn><mspace/>" };
+        assert_eq!(
+            String::from(math),
+            "<mi>x</mi><mo>=</mo><mn>2</mn><mspace/>"
+        );
+    }
+
+    #[test]
+    fn test_svg_href_is_safe_url() {
+        let unsafe_url = "javascript:alert(1)";
+        let svg = rhtml! { r#"<svg><a href="{unsafe_url}"><text>Link</text></a></svg>"# };
+        assert_eq!(
+            String::from(svg),
+            r#"<svg><a href="about:invalid"><text>Link</text></a></svg>"#
+        );
+
+        let icon = "#icon";
+        let url = "https://example.com";
+        let svg = rhtml! { r##"<svg><use xlink:href="{icon}"/><a href="{url}"></a></svg>"## };
+        assert_eq!(
+            String::from(svg),
+            r##"<svg><use xlink:href="#icon"/><a href="https:&#x2F;&#x2F;example.com"></a></svg>"##
+        );
+    }
+
+    #[test]
+    fn test_svg_fragment_href_is_safe_url() {
+        let unsafe_url = Unescaped(" \tJava\nScript:alert(1)".to_string());
+        let svg = rhtml! { r#"<use href="{unsafe_url}"/>"# };
+        assert_eq!(String::from(svg), r#"<use href="about:invalid"/>"#);
+    }
+
+    #[test]
+    fn test_html_href_is_unchanged() {
+        let url = "javascript:void(0)";
+        let link = rhtml! { r#"<a href="{url}">Link</a>"# };
+        assert_eq!(
+            String::from(link),
+            r#"<a href="javascript:void(0)">Link</a>"#
+        );
+
+        let link = rhtml! { r#"<a href="{SafeUrl(url)}">Link</a>"# };
+        assert_eq!(String::from(link), r#"<a href="about:invalid">Link</a>"#);
+    }
+
+    #[test]
+    fn test_safe_url_schemes() {
+        for url in [
+            "/a",
+            "#b",
+            "?c",
+            "d.html",
+            "http://e",
+            "HTTPS://f",
+            "mailto:g",
+            "tel:1",
+        ] {
+            assert_eq!(
+                String::from(SafeUrl(Unescaped(url.to_string())).render()),
+                url
+            );
+        }
+        for url in [
+            "javascript:x",
+            "data:text/html,x",
+            "vbscript:x",
+            "JaVaScRiPt:x",
+        ] {
+            let rendered = String::from(SafeUrl(Unescaped(url.to_string())).render());
+            assert_eq!(rendered, "about:invalid", "{}", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_svg_href_is_safe_url() {
+        let url = async { "javascript:alert(1)" };
+        let svg = rhtml_async! { r#"<svg><a href="{url.await}"></a></svg>"# }.await;
+        assert_eq!(
+            String::from(svg),
+            r#"<svg><a href="about:invalid"></a></svg>"#
+        );
+    }
+}
@@ -0,0 +1,22 @@
+// Unit tests for the warp integration
+#[cfg(test)]
+mod test {
+    use rust_html::*;
+    use warp::http::header;
+    use warp::Filter;
+
+    #[tokio::test]
+    async fn test_template_reply() {
+        let filter = warp::path::end().map(|| {
+            let value = "<hello>";
+            rhtml! { "<div>{value}</div>" }
+        });
+        let response = warp::test::request().path("/").reply(&filter).await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(response.body(), "<div>&lt;hello&gt;</div>");
+    }
+}
@@ -0,0 +1,30 @@
+// Unit tests for the WebSocket integration
+#[cfg(test)]
+mod test {
+    use axum::extract::ws::Message;
+    use futures_util::{stream, StreamExt};
+    use rust_html::integration::send_templates;
+    use rust_html::*;
+
+    #[test]
+    fn test_template_message() {
+        let value = "<hello>";
+        let message = Message::from(rhtml! { "<div>{value}</div>" });
+        assert_eq!(message, Message::text("<div>&lt;hello&gt;</div>"));
+    }
+
+    #[tokio::test]
+    async fn test_send_templates() {
+        let templates = stream::iter(1..=3).map(|i| rhtml! { "<li>{i}</li>" });
+        let mut socket: Vec<Message> = vec![];
+        send_templates(templates, &mut socket).await.unwrap();
+        assert_eq!(
+            socket,
+            vec![
+                Message::text("<li>1</li>"),
+                Message::text("<li>2</li>"),
+                Message::text("<li>3</li>"),
+            ]
+        );
+    }
+}
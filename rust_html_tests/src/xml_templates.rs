@@ -0,0 +1,178 @@
+// Unit tests for XML templates and feeds
+#[cfg(test)]
+mod test {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+    use http_body_util::BodyExt;
+    use rust_html::xml::*;
+    use rust_html::*;
+
+    #[test]
+    fn test_rxml_escaping() {
+        let value = "<a href='x'>\"Tom\" & Jerry</a>";
+        let xml = rxml! { r#"<item title="{value}">{value}</item>"# };
+        let escaped = "&lt;a href=&apos;x&apos;&gt;&quot;Tom&quot; &amp; Jerry&lt;/a&gt;";
+        assert_eq!(
+            String::from(xml),
+            format!("<item title=\"{}\">{}</item>", escaped, escaped)
+        );
+    }
+
+    #[test]
+    fn test_rxml_invalid_characters() {
+        let value = "a\u{0}b\u{1b}c\td";
+        assert_eq!(String::from(rxml! { "<a>{value}</a>" }), "<a>abc\td</a>");
+    }
+
+    #[test]
+    fn test_rxml_components() {
+        let items: XmlTemplate = (1..=2).map(|i| rxml! { "<item>{i}</item>" }).collect();
+        let html = rhtml! { "<p>Hello</p>" };
+        let raw = Unescaped("<raw/>".to_string());
+        let xml = rxml! { "<list>{items}<html>{html}</html>{raw}</list>" };
+        assert_eq!(
+            String::from(xml),
+            "<list><item>1</item><item>2</item><html>&lt;p&gt;Hello&lt;/p&gt;</html><raw/></list>"
+        );
+    }
+
+    #[test]
+    fn test_rxml_declaration() {
+        let xml = rxml! { r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <root/>
+        "# };
+        let xml = String::from(xml);
+        assert!(xml.starts_with("<?xml"));
+        assert_eq!(
+            String::from(rxml! { "\n<a/>" }),
+            "\n<a/>",
+            "only whitespace before a declaration is removed"
+        );
+    }
+
+    #[test]
+    fn test_sitemap() {
+        let sitemap = Sitemap::new()
+            .url("https://example.com/")
+            .url(
+                SitemapUrl::new("https://example.com/blog?a&b")
+                    .last_modified("2025-01-06")
+                    .change_frequency(ChangeFrequency::Daily)
+                    .priority(0.75),
+            )
+            .render();
+        assert_eq!(sitemap.content_type(), "application/xml; charset=utf-8");
+        assert_eq!(
+            String::from(sitemap),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url>
+    <loc>https://example.com/</loc>
+  </url>
+  <url>
+    <loc>https://example.com/blog?a&amp;b</loc><lastmod>2025-01-06</lastmod><changefreq>daily</changefreq><priority>0.8</priority>
+  </url>
+</urlset>
+"#
+        );
+    }
+
+    #[test]
+    fn test_sitemap_priority() {
+        let priority = |priority: f32| {
+            let sitemap = Sitemap::new()
+                .url(SitemapUrl::new("https://example.com/").priority(priority))
+                .render();
+            String::from(sitemap)
+        };
+        assert!(priority(2.0).contains("<priority>1.0</priority>"));
+        assert!(priority(-1.0).contains("<priority>0.0</priority>"));
+        assert!(!priority(f32::NAN).contains("<priority>"));
+        assert!(!priority(f32::INFINITY).contains("<priority>"));
+    }
+
+    #[test]
+    fn test_rss_feed() {
+        let feed = RssFeed::new("Blog", "https://example.com", "Posts & news")
+            .language("en-us")
+            .self_link("https://example.com/rss.xml")
+            .item(
+                RssItem::new("Hello")
+                    .link("https://example.com/hello")
+                    .description(rhtml! { "<p>First post</p>" })
+                    .guid("https://example.com/hello")
+                    .pub_date("Mon, 06 Jan 2025 12:00:00 GMT"),
+            )
+            .item(RssItem::new("Second").description("plain text"))
+            .render();
+        assert_eq!(feed.content_type(), "application/rss+xml; charset=utf-8");
+        assert_eq!(
+            String::from(feed),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+  <channel>
+    <title>Blog</title>
+    <link>https://example.com</link>
+    <description>Posts &amp; news</description><atom:link href="https://example.com/rss.xml" rel="self" type="application/rss+xml"/><language>en-us</language>
+    <item>
+      <title>Hello</title><link>https://example.com/hello</link><description>&lt;p&gt;First post&lt;/p&gt;</description><guid>https://example.com/hello</guid><pubDate>Mon, 06 Jan 2025 12:00:00 GMT</pubDate>
+    </item>
+    <item>
+      <title>Second</title><description>plain text</description>
+    </item>
+  </channel>
+</rss>
+"#
+        );
+    }
+
+    #[test]
+    fn test_atom_feed() {
+        let feed = AtomFeed::new("https://example.com/", "Blog", "2025-01-06T12:00:00Z")
+            .link("https://example.com/")
+            .author("Jane")
+            .entry(
+                AtomEntry::new("https://example.com/hello", "Hello", "2025-01-06T12:00:00Z")
+                    .summary("Summary <b>")
+                    .content(rhtml! { "<p>Content</p>" }),
+            )
+            .render();
+        assert_eq!(feed.content_type(), "application/atom+xml; charset=utf-8");
+        assert_eq!(
+            String::from(feed),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>https://example.com/</id>
+  <title>Blog</title>
+  <updated>2025-01-06T12:00:00Z</updated><link href="https://example.com/"/><author><name>Jane</name></author>
+  <entry>
+    <id>https://example.com/hello</id>
+    <title>Hello</title>
+    <updated>2025-01-06T12:00:00Z</updated><summary>Summary &lt;b&gt;</summary><content type="html">&lt;p&gt;Content&lt;/p&gt;</content>
+  </entry>
+</feed>
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_xml_response() {
+        let feed = RssFeed::new("Blog", "https://example.com", "Posts").render();
+        let response = feed.clone().into_response();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/rss+xml; charset=utf-8"
+        );
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, String::from(feed));
+
+        let response = rxml! { "<a/>" }
+            .with_content_type("image/svg+xml")
+            .into_response();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "image/svg+xml"
+        );
+    }
+}
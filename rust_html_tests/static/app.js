@@ -0,0 +1 @@
+console.log("rust_html");
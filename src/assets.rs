@@ -0,0 +1,158 @@
+//! Fingerprinted static assets
+//!
+//! The `asset!` macro hashes a static file at compile time. Its URL
+//! contains the hash, so it changes whenever the file does and browsers
+//! can cache it forever. The hash is also used for subresource integrity.
+//! Paths are relative to the crate root, here the root of this repository:
+//!
+//! ```rust no_run
+//! use rust_html::{asset, rhtml, Asset, Template};
+//!
+//! const APP_JS: Asset = asset!("rust_html_tests/static/app.js");
+//! const STYLE_CSS: Asset = asset!("rust_html_tests/static/style.css");
+//!
+//! fn layout(content: Template) -> Template {
+//!     rhtml! { r#"
+//!         <!DOCTYPE html>
+//!         <html lang="en">
+//!             <head>{STYLE_CSS.stylesheet()}{APP_JS.script()}</head>
+//!             <body>{content}</body>
+//!         </html>
+//!     "# }
+//! }
+//! ```
+//!
+//! With the `assets` feature, `asset_router` serves the assets from
+//! the binary at their URLs.
+
+use std::fmt;
+
+use crate::{rhtml, SafeUrl, Template};
+
+/// Static file hashed at compile time, created with the `asset!` macro
+///
+/// Renders as its fingerprinted URL, e.g. `/assets/app.0123456789.js`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Asset {
+    /// Path of the file, relative to the crate root
+    pub path: &'static str,
+    /// URL with the hash of the file, under `/assets/`
+    pub url: &'static str,
+    /// Subresource integrity, e.g. `sha384-...`
+    pub integrity: &'static str,
+    /// Contents of the file, embedded in the binary
+    pub contents: &'static [u8],
+}
+
+impl Asset {
+    /// Content type by file extension, `application/octet-stream` if unknown
+    pub fn content_type(&self) -> &'static str {
+        let extension = self.url.rsplit_once('.').map(|(_, extension)| extension);
+        match extension.unwrap_or_default().to_ascii_lowercase().as_str() {
+            "css" => "text/css; charset=utf-8",
+            "js" | "mjs" => "text/javascript; charset=utf-8",
+            "json" | "map" => "application/json",
+            "wasm" => "application/wasm",
+            "html" | "htm" => "text/html; charset=utf-8",
+            "txt" => "text/plain; charset=utf-8",
+            "xml" => "application/xml",
+            "svg" => "image/svg+xml",
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "webp" => "image/webp",
+            "avif" => "image/avif",
+            "ico" => "image/x-icon",
+            "woff" => "font/woff",
+            "woff2" => "font/woff2",
+            "ttf" => "font/ttf",
+            "otf" => "font/otf",
+            "pdf" => "application/pdf",
+            "mp4" => "video/mp4",
+            "webm" => "video/webm",
+            "mp3" => "audio/mpeg",
+            _ => "application/octet-stream",
+        }
+    }
+
+    /// `<script>` tag for the asset, with its integrity
+    pub fn script(&self) -> Template {
+        let src = SafeUrl(self.url);
+        let integrity = self.integrity;
+        rhtml! { r#"<script src="{src}" integrity="{integrity}" crossorigin="anonymous"></script>"# }
+    }
+
+    /// `<link rel="stylesheet">` tag for the asset, with its integrity
+    pub fn stylesheet(&self) -> Template {
+        let href = SafeUrl(self.url);
+        let integrity = self.integrity;
+        rhtml! { r#"<link rel="stylesheet" href="{href}" integrity="{integrity}" crossorigin="anonymous">"# }
+    }
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.url)
+    }
+}
+
+#[cfg(feature = "assets")]
+pub use router::asset_router;
+
+#[cfg(feature = "assets")]
+mod router {
+    use std::collections::HashSet;
+
+    use axum::http::{header, HeaderValue};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+
+    use super::Asset;
+
+    /// Assets never change at their URL, since it contains their hash
+    const IMMUTABLE: HeaderValue = HeaderValue::from_static("public, max-age=31536000, immutable");
+
+    /// Axum router serving each asset at its URL, with
+    /// headers for caching it for as long as possible
+    ///
+    /// Merge it into the app with `Router::merge`. Assets that
+    /// are listed more than once are only routed once.
+    ///
+    /// ```rust no_run
+    /// use axum::{routing::get, Router};
+    /// use rust_html::{asset, assets::asset_router, rhtml, Asset, Template};
+    ///
+    /// const APP_JS: Asset = asset!("rust_html_tests/static/app.js");
+    /// const STYLE_CSS: Asset = asset!("rust_html_tests/static/style.css");
+    ///
+    /// async fn root() -> Template {
+    ///     rhtml! { "<h1>Hello</h1>" }
+    /// }
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/", get(root))
+    ///     .merge(asset_router([APP_JS, STYLE_CSS]));
+    /// ```
+    pub fn asset_router<S>(assets: impl IntoIterator<Item = Asset>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let mut urls = HashSet::new();
+        let assets = assets.into_iter().filter(|asset| urls.insert(asset.url));
+        assets.fold(Router::new(), |router, asset| {
+            let handler = move || async move {
+                let content_type = HeaderValue::from_static(asset.content_type());
+                (
+                    [
+                        (header::CONTENT_TYPE, content_type),
+                        (header::CACHE_CONTROL, IMMUTABLE),
+                    ],
+                    asset.contents,
+                )
+                    .into_response()
+            };
+            router.route(asset.url, get(handler))
+        })
+    }
+}
@@ -0,0 +1,305 @@
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::{Render, Template, TemplateContent};
+
+/// Boxed future resolving to a rendered template
+///
+/// This is the type of each placeholder inside an
+/// `AsyncTemplate`, and the future returned when
+/// awaiting one.
+pub type RenderFuture<'a> = Pin<Box<dyn Future<Output = Template> + Send + 'a>>;
+
+/// Async render trait for rust_html templates
+///
+/// Implement this trait on a struct to create components
+/// that need to await data (e.g. a database lookup) before
+/// they can be rendered. Any future whose output implements
+/// `Render` already implements this trait.
+///
+/// Async components are inserted with `.await` inside
+/// the `rhtml_async!` macro:
+///
+/// ```rust
+/// use rust_html::{rhtml, rhtml_async, AsyncRender, Template};
+///
+/// struct UserCard {
+///     id: u32,
+/// }
+///
+/// impl AsyncRender for UserCard {
+///     async fn render_async(self) -> Template {
+///         let name = format!("user {}", self.id); // e.g. a database lookup
+///         rhtml! { "<div>{name}</div>" }
+///     }
+/// }
+///
+/// async fn page() -> Template {
+///     let card = UserCard { id: 1 };
+///     rhtml_async! { "<main>{card.await}</main>" }.await
+/// }
+/// ```
+pub trait AsyncRender {
+    fn render_async(self) -> impl Future<Output = Template> + Send;
+
+    /// Renders the value inside a quoted attribute value, which the
+    /// `rhtml_async!` macro uses for placeholders like `<div title="{value.await}">`.
+    /// See `Render::render_attribute`.
+    fn render_attribute_async(self) -> impl Future<Output = Template> + Send
+    where
+        Self: Sized,
+    {
+        self.render_async()
+    }
+}
+
+/// Struct representing a rust_html template with async placeholders.
+///
+/// Create one using the `rust_html::rhtml_async!` macro. Placeholders
+/// ending in `.await` are rendered concurrently when the template
+/// itself is awaited:
+///
+/// ```rust
+/// use rust_html::{rhtml_async, Template};
+///
+/// async fn title() -> &'static str {
+///     "Hello!"
+/// }
+///
+/// async fn page() -> Template {
+///     rhtml_async! { "<h1>{title().await}</h1>" }.await
+/// }
+/// ```
+///
+pub struct AsyncTemplate<'a> {
+    template_parts: Vec<(&'static str, RenderFuture<'a>)>,
+    template_end: &'static str,
+}
+
+impl<'a> AsyncTemplate<'a> {
+    /// Internal macro creation of a rust_html async template.
+    ///
+    /// DO NOT USE THIS.
+    /// USE THE `rhtml_async!` MACRO.
+    ///
+    /// This implementation is low level and intended
+    /// to be used by the rust_html_macros crate.
+    pub fn build_internal(
+        template_parts: Vec<(&'static str, RenderFuture<'a>)>,
+        template_end: &'static str,
+    ) -> Self {
+        AsyncTemplate {
+            template_parts,
+            template_end,
+        }
+    }
+}
+
+impl<'a> IntoFuture for AsyncTemplate<'a> {
+    type Output = Template;
+    type IntoFuture = RenderFuture<'a>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let template_parts = self
+            .template_parts
+            .into_iter()
+            .map(|(html_part, future)| (html_part, PartState::Pending(future)))
+            .collect();
+        Box::pin(RenderJoin {
+            template_parts,
+            template_end: self.template_end,
+        })
+    }
+}
+
+impl<F> AsyncRender for F
+where
+    F: IntoFuture + Send,
+    F::IntoFuture: Send,
+    F::Output: Render,
+{
+    fn render_async(self) -> impl Future<Output = Template> + Send {
+        let future = self.into_future();
+        async move { future.await.render() }
+    }
+
+    fn render_attribute_async(self) -> impl Future<Output = Template> + Send {
+        let future = self.into_future();
+        async move { future.await.render_attribute() }
+    }
+}
+
+enum PartState<'a> {
+    Pending(RenderFuture<'a>),
+    Ready(Template),
+}
+
+/// Internal future polling all placeholders of
+/// an async template concurrently
+struct RenderJoin<'a> {
+    template_parts: Vec<(&'static str, PartState<'a>)>,
+    template_end: &'static str,
+}
+
+impl Future for RenderJoin<'_> {
+    type Output = Template;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Template> {
+        let mut all_ready = true;
+        for (_, part) in self.template_parts.iter_mut() {
+            if let PartState::Pending(future) = part {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(template) => *part = PartState::Ready(template),
+                    Poll::Pending => all_ready = false,
+                }
+            }
+        }
+        if !all_ready {
+            return Poll::Pending;
+        }
+        let template_parts = std::mem::take(&mut self.template_parts)
+            .into_iter()
+            .map(|(html_part, part)| match part {
+                PartState::Ready(template) => (html_part, template),
+                PartState::Pending(_) => unreachable!("all parts are ready"),
+            })
+            .collect();
+        Poll::Ready(Template::build_internal(template_parts, self.template_end))
+    }
+}
+
+/// Streamed chunks are flushed once they reach this size
+/// (or earlier, when the next part is still pending)
+const STREAM_CHUNK_SIZE: usize = 8 * 1024;
+
+enum StreamPart<'a> {
+    Static(&'static str),
+    Owned(String),
+    Pending(RenderFuture<'a>),
+}
+
+/// Stream of HTML chunks for a rust_html template
+///
+/// Chunks are produced in document order. Everything up to the
+/// first pending placeholder is available immediately, while all
+/// pending placeholders are polled concurrently. With the `axum`
+/// feature this is also a streaming response body.
+///
+/// ```rust
+/// use rust_html::{rhtml_async, TemplateStream};
+///
+/// async fn page() -> String {
+///     let slow = async { "slow content" };
+///     let template = rhtml_async! { "<h1>Title</h1>{slow.await}" };
+///     let mut stream = TemplateStream::from(template);
+///     let mut html = String::new();
+///     while let Some(chunk) = stream.next_chunk().await {
+///         html.push_str(&chunk);
+///     }
+///     html
+/// }
+/// ```
+///
+pub struct TemplateStream<'a> {
+    parts: Vec<StreamPart<'a>>,
+}
+
+impl TemplateStream<'_> {
+    /// Returns the next chunk of HTML, or `None` when
+    /// the whole template has been streamed
+    pub async fn next_chunk(&mut self) -> Option<String> {
+        std::future::poll_fn(|cx| self.poll_next_chunk(cx)).await
+    }
+
+    /// Polls for the next chunk of HTML, see `next_chunk`
+    pub fn poll_next_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<String>> {
+        // Drive all pending placeholders, replacing resolved ones with their content
+        let mut index = 0;
+        while index < self.parts.len() {
+            if let StreamPart::Pending(future) = &mut self.parts[index] {
+                if let Poll::Ready(template) = future.as_mut().poll(cx) {
+                    let mut resolved = vec![];
+                    flatten_template(template, &mut resolved);
+                    let resolved_len = resolved.len();
+                    self.parts.splice(index..index + 1, resolved);
+                    index += resolved_len;
+                    continue;
+                }
+            }
+            index += 1;
+        }
+
+        // Emit leading parts that are ready
+        let mut chunk_len = 0;
+        let mut ready_parts = 0;
+        for part in self.parts.iter() {
+            if chunk_len >= STREAM_CHUNK_SIZE {
+                break;
+            }
+            chunk_len += match part {
+                StreamPart::Static(html) => html.len(),
+                StreamPart::Owned(html) => html.len(),
+                StreamPart::Pending(_) => break,
+            };
+            ready_parts += 1;
+        }
+        if ready_parts > 0 {
+            let mut chunk = String::with_capacity(chunk_len);
+            for part in self.parts.drain(..ready_parts) {
+                match part {
+                    StreamPart::Static(html) => chunk.push_str(html),
+                    StreamPart::Owned(html) => chunk.push_str(&html),
+                    StreamPart::Pending(_) => unreachable!("only ready parts are drained"),
+                }
+            }
+            // Empty parts before a pending placeholder would be an empty chunk
+            if !chunk.is_empty() {
+                return Poll::Ready(Some(chunk));
+            }
+        }
+        if self.parts.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl From<Template> for TemplateStream<'_> {
+    fn from(template: Template) -> Self {
+        let mut parts = vec![];
+        flatten_template(template, &mut parts);
+        TemplateStream { parts }
+    }
+}
+
+impl<'a> From<AsyncTemplate<'a>> for TemplateStream<'a> {
+    fn from(template: AsyncTemplate<'a>) -> Self {
+        let mut parts = Vec::with_capacity(template.template_parts.len() * 2 + 1);
+        for (html_part, future) in template.template_parts {
+            parts.push(StreamPart::Static(html_part));
+            parts.push(StreamPart::Pending(future));
+        }
+        parts.push(StreamPart::Static(template.template_end));
+        TemplateStream { parts }
+    }
+}
+
+/// Walks the template tree and appends its parts in document order
+fn flatten_template(template: Template, output: &mut Vec<StreamPart<'_>>) {
+    match template.content {
+        TemplateContent::RawString(value) => output.push(StreamPart::Owned(value)),
+        TemplateContent::ScopedStyle(_) | TemplateContent::Head(_) => {}
+        TemplateContent::WithParameters {
+            template_parts,
+            template_end,
+        } => {
+            for (html_part, param_part) in template_parts {
+                output.push(StreamPart::Static(html_part));
+                flatten_template(param_part, output);
+            }
+            output.push(StreamPart::Static(template_end));
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! Forms generated from structs with `#[derive(Form)]`
+//!
+//! Each field of the struct is rendered as a labelled input. The input
+//! type depends on the field type, see `FormField`. `Option` fields are
+//! not required, and enums deriving `Form` are rendered as a `<select>`:
+//!
+//! ```rust
+//! use rust_html::form::{Form, FormErrors};
+//! use rust_html::{rhtml, Form};
+//!
+//! #[derive(Form)]
+//! enum Role {
+//!     Admin,
+//!     #[form(label = "Regular user")]
+//!     User,
+//! }
+//!
+//! #[derive(Form)]
+//! struct Account {
+//!     #[form(label = "Full name", placeholder = "Jane Doe", minlength = 2)]
+//!     name: String,
+//!     #[form(input_type = "email")]
+//!     email: String,
+//!     age: Option<u32>,
+//!     role: Role,
+//! }
+//!
+//! let account = Account {
+//!     name: "J".to_string(),
+//!     email: "jane@example.com".to_string(),
+//!     age: None,
+//!     role: Role::User,
+//! };
+//! let mut errors = FormErrors::new();
+//! errors.add("name", "Name is too short");
+//! let fields = account.form_with_errors(&errors);
+//! let page = rhtml! { r#"<form method="post">{fields}<button>Save</button></form>"# };
+//! ```
+//!
+//! Input names follow serde's `rename` and `rename_all` attributes, so
+//! that the submitted form can be deserialized into the struct again.
+
+use std::collections::BTreeMap;
+
+use crate::{rhtml, Template, TemplateGroup, Unescaped};
+
+/// Struct rendered as form fields, implemented with `#[derive(Form)]`
+pub trait Form {
+    /// Renders a labelled input for each field, pre-filled with `values`
+    /// if given, and with the messages in `errors` below their field
+    fn form_fields(values: Option<&Self>, errors: &FormErrors) -> Template;
+
+    /// Renders the fields of an empty form, e.g. to create a new value
+    fn empty_form() -> Template {
+        Self::form_fields(None, &FormErrors::new())
+    }
+
+    /// Renders the fields pre-filled with the value, e.g. to edit it
+    fn form(&self) -> Template {
+        Self::form_fields(Some(self), &FormErrors::new())
+    }
+
+    /// Renders the fields pre-filled with the value and the error
+    /// messages, e.g. after a submitted form failed validation
+    fn form_with_errors(&self, errors: &FormErrors) -> Template {
+        Self::form_fields(Some(self), errors)
+    }
+}
+
+/// Type of a form field, deciding how its input is rendered
+///
+/// Implemented for strings, numbers, `bool` (a checkbox) and `Option`
+/// (not required). `#[derive(Form)]` on an enum with unit variants
+/// implements it as a `<select>` of the variants.
+pub trait FormField {
+    /// `type` of the `<input>`
+    fn input_type() -> &'static str {
+        "text"
+    }
+
+    /// Extra attributes of the input, e.g. `step="any"` for floats
+    fn input_attributes() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Whether the field must be filled in
+    fn required() -> bool {
+        true
+    }
+
+    /// Options of a `<select>` as `(value, label)`, used instead of an `<input>`
+    fn select_options() -> Option<&'static [(&'static str, &'static str)]> {
+        None
+    }
+
+    /// Value the input is pre-filled with
+    fn form_value(&self) -> String;
+}
+
+/// Field of a struct deriving `Form`, with the settings from its `#[form(...)]` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpec {
+    /// Name of the input, also used as its id
+    pub name: &'static str,
+    pub label: &'static str,
+    /// Overrides `FormField::input_type`, e.g. `email`, `textarea` or `hidden`
+    pub input_type: Option<&'static str>,
+    pub placeholder: Option<&'static str>,
+    /// Overrides `FormField::required`
+    pub required: Option<bool>,
+    /// Validation attributes, e.g. `("maxlength", "100")`
+    pub attributes: &'static [(&'static str, &'static str)],
+}
+
+/// Error messages of form fields, by input name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormErrors {
+    errors: BTreeMap<String, Vec<String>>,
+}
+
+impl FormErrors {
+    pub fn new() -> FormErrors {
+        FormErrors::default()
+    }
+
+    /// Adds an error message for the field with the given input name
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors
+            .entry(field.into())
+            .or_default()
+            .push(message.into());
+    }
+
+    /// Error messages of a field, in the order they were added
+    pub fn get(&self, field: &str) -> &[String] {
+        self.errors
+            .get(field)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Renders the label, input and error messages of a field
+pub fn render_field<T: FormField + ?Sized>(
+    spec: &FieldSpec,
+    value: Option<&T>,
+    errors: &FormErrors,
+) -> Template {
+    let input_type = spec.input_type.unwrap_or(T::input_type());
+    let value = value.map(T::form_value).unwrap_or_default();
+    let id = spec.name;
+    let label = spec.label;
+
+    if input_type == "hidden" {
+        let attributes = Unescaped(attributes(&[
+            ("type", Some("hidden")),
+            ("name", Some(id)),
+            ("value", Some(&value)),
+        ]));
+        return rhtml! { "<input {attributes}>" };
+    }
+
+    let select_options = T::select_options();
+    let is_input = select_options.is_none() && input_type != "textarea";
+    let is_checkbox = is_input && input_type == "checkbox";
+    let required = spec.required.unwrap_or(T::required());
+    let messages = errors.get(spec.name);
+    let error_id = format!("{}-errors", id);
+
+    let mut control_attributes = vec![("id", Some(id)), ("name", Some(id))];
+    if is_input {
+        control_attributes.push(("type", Some(input_type)));
+    }
+    if is_checkbox {
+        control_attributes.push(("value", Some("true")));
+        if value == "true" {
+            control_attributes.push(("checked", None));
+        }
+    } else if is_input {
+        control_attributes.push(("value", Some(&value)));
+    }
+    if let Some(placeholder) = spec.placeholder {
+        control_attributes.push(("placeholder", Some(placeholder)));
+    }
+    if required {
+        control_attributes.push(("required", None));
+    }
+    let validation = T::input_attributes().iter().chain(spec.attributes);
+    control_attributes.extend(validation.map(|(name, value)| (*name, Some(*value))));
+    if !messages.is_empty() {
+        control_attributes.push(("aria-invalid", Some("true")));
+        control_attributes.push(("aria-describedby", Some(&error_id)));
+    }
+    let control_attributes = Unescaped(attributes(&control_attributes));
+
+    let control = if let Some(options) = select_options {
+        let empty_option = if required {
+            Template::from("")
+        } else {
+            rhtml! { r#"<option value=""></option>"# }
+        };
+        let options: TemplateGroup = options
+            .iter()
+            .map(|(option, label)| {
+                if *option == value {
+                    rhtml! { r#"<option value="{option}" selected>{label}</option>"# }
+                } else {
+                    rhtml! { r#"<option value="{option}">{label}</option>"# }
+                }
+            })
+            .collect();
+        rhtml! { "<select {control_attributes}>{empty_option}{options}</select>" }
+    } else if input_type == "textarea" {
+        // The newline keeps a leading newline of the value, which would be dropped
+        rhtml! { "<textarea {control_attributes}>\n{value}</textarea>" }
+    } else {
+        rhtml! { "<input {control_attributes}>" }
+    };
+
+    let errors = if messages.is_empty() {
+        Template::from("")
+    } else {
+        let items: TemplateGroup = messages
+            .iter()
+            .map(|message| rhtml! { "<li>{message}</li>" })
+            .collect();
+        rhtml! { r#"<ul class="form-errors" id="{error_id}">{items}</ul>"# }
+    };
+
+    rhtml! { r#"<div class="form-field"><label for="{id}">{label}</label>{control}{errors}</div>"# }
+}
+
+/// Escaped attribute list, separated by spaces
+fn attributes(attributes: &[(&str, Option<&str>)]) -> String {
+    let mut html = String::new();
+    for (name, value) in attributes {
+        if !html.is_empty() {
+            html.push(' ');
+        }
+        html.push_str(name);
+        if let Some(value) = value {
+            html.push_str("=\"");
+            html.push_str(&html_escape::encode_safe(value));
+            html.push('"');
+        }
+    }
+    html
+}
+
+impl FormField for String {
+    fn form_value(&self) -> String {
+        self.clone()
+    }
+}
+
+impl FormField for str {
+    fn form_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T: FormField + ?Sized> FormField for &T {
+    fn input_type() -> &'static str {
+        T::input_type()
+    }
+
+    fn input_attributes() -> &'static [(&'static str, &'static str)] {
+        T::input_attributes()
+    }
+
+    fn required() -> bool {
+        T::required()
+    }
+
+    fn select_options() -> Option<&'static [(&'static str, &'static str)]> {
+        T::select_options()
+    }
+
+    fn form_value(&self) -> String {
+        T::form_value(self)
+    }
+}
+
+impl FormField for char {
+    fn input_attributes() -> &'static [(&'static str, &'static str)] {
+        &[("maxlength", "1")]
+    }
+
+    fn form_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FormField for bool {
+    fn input_type() -> &'static str {
+        "checkbox"
+    }
+
+    /// An unchecked checkbox is `false`, a required one must be checked
+    fn required() -> bool {
+        false
+    }
+
+    fn form_value(&self) -> String {
+        if *self { "true" } else { "" }.to_string()
+    }
+}
+
+macro_rules! integer_form_field {
+    ($($integer:ty),*) => {$(
+        impl FormField for $integer {
+            fn input_type() -> &'static str {
+                "number"
+            }
+
+            fn form_value(&self) -> String {
+                self.to_string()
+            }
+        }
+    )*};
+}
+
+integer_form_field!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! float_form_field {
+    ($($float:ty),*) => {$(
+        impl FormField for $float {
+            fn input_type() -> &'static str {
+                "number"
+            }
+
+            fn input_attributes() -> &'static [(&'static str, &'static str)] {
+                &[("step", "any")]
+            }
+
+            fn form_value(&self) -> String {
+                self.to_string()
+            }
+        }
+    )*};
+}
+
+float_form_field!(f32, f64);
+
+impl<T: FormField> FormField for Option<T> {
+    fn input_type() -> &'static str {
+        T::input_type()
+    }
+
+    fn input_attributes() -> &'static [(&'static str, &'static str)] {
+        T::input_attributes()
+    }
+
+    fn required() -> bool {
+        false
+    }
+
+    fn select_options() -> Option<&'static [(&'static str, &'static str)]> {
+        T::select_options()
+    }
+
+    fn form_value(&self) -> String {
+        self.as_ref().map(T::form_value).unwrap_or_default()
+    }
+}
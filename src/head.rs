@@ -0,0 +1,136 @@
+//! Dependencies of components for the page `<head>`
+//!
+//! Components declare the stylesheets, scripts and preloads they need
+//! with `Template::with_head`. The dependencies are kept in the template
+//! tree, and `Template::head` renders each of them once in the layout:
+//!
+//! ```rust
+//! use rust_html::{rhtml, HeadItem, Template};
+//!
+//! fn chart(id: &str) -> Template {
+//!     rhtml! { r#"<canvas class="chart" data-id="{id}"></canvas>"# }
+//!         .with_head(HeadItem::Stylesheet("/static/chart.css".to_string()))
+//!         .with_head(HeadItem::DeferredScript("/static/chart.js".to_string()))
+//! }
+//!
+//! let content = rhtml! { "<main>{chart(\"a\")}{chart(\"b\")}</main>" };
+//! let page = rhtml! { r#"
+//!     <!DOCTYPE html>
+//!     <html lang="en">
+//!         <head>{content.head()}</head>
+//!         <body>{content}</body>
+//!     </html>
+//! "# };
+//! let html = String::from(page);
+//! assert_eq!(html.matches("chart.js").count(), 1);
+//! ```
+
+use crate::{rhtml, Render, SafeUrl, Template, TemplateContent, TemplateGroup, Unescaped};
+
+/// Stylesheet, script or preload needed by a component, see `Template::with_head`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HeadItem {
+    /// `<link rel="stylesheet" href="...">`
+    Stylesheet(String),
+    /// `<script src="..."></script>`
+    Script(String),
+    /// `<script src="..." defer></script>`
+    DeferredScript(String),
+    /// `<script type="module" src="..."></script>`
+    ModuleScript(String),
+    /// `<link rel="preload" href="..." as="...">`, e.g. `as` is `font` or `image`
+    Preload { href: String, kind: String },
+    /// Inline `<style>` with trusted CSS
+    Style(&'static str),
+    /// Inline `<script>` with trusted JavaScript
+    InlineScript(&'static str),
+}
+
+/// Renders the tag for the `<head>`
+impl Render for HeadItem {
+    fn render(&self) -> Template {
+        match self {
+            HeadItem::Stylesheet(href) => {
+                let href = SafeUrl(href);
+                rhtml! { r#"<link rel="stylesheet" href="{href}">"# }
+            }
+            HeadItem::Script(src) => {
+                let src = SafeUrl(src);
+                rhtml! { r#"<script src="{src}"></script>"# }
+            }
+            HeadItem::DeferredScript(src) => {
+                let src = SafeUrl(src);
+                rhtml! { r#"<script src="{src}" defer></script>"# }
+            }
+            HeadItem::ModuleScript(src) => {
+                let src = SafeUrl(src);
+                rhtml! { r#"<script type="module" src="{src}"></script>"# }
+            }
+            HeadItem::Preload { href, kind } => {
+                let href = SafeUrl(href);
+                // Fonts are always fetched in CORS mode
+                if kind == "font" {
+                    rhtml! { r#"<link rel="preload" href="{href}" as="font" crossorigin>"# }
+                } else {
+                    rhtml! { r#"<link rel="preload" href="{href}" as="{kind}">"# }
+                }
+            }
+            HeadItem::Style(css) => {
+                let css = Unescaped(css.to_string());
+                rhtml! { "<style>{css}</style>" }
+            }
+            HeadItem::InlineScript(script) => {
+                let script = Unescaped(script.to_string());
+                rhtml! { "<script>{script}</script>" }
+            }
+        }
+    }
+}
+
+impl Template {
+    /// Adds a dependency for the page `<head>` to the template. It
+    /// renders nothing where the template is used, and is collected
+    /// from the whole template tree by `Template::head`.
+    pub fn with_head(mut self, item: HeadItem) -> Template {
+        let head = Template {
+            content: TemplateContent::Head(item),
+        };
+        // Keep the order of chained calls, after the items added before
+        if let TemplateContent::WithParameters {
+            template_parts,
+            template_end: "",
+        } = &mut self.content
+        {
+            let is_head = |(html, template): &(&str, Template)| {
+                html.is_empty() && matches!(template.content, TemplateContent::Head(_))
+            };
+            if let Some((_, items)) = template_parts.split_last() {
+                if !items.is_empty() && items.iter().all(is_head) {
+                    template_parts.insert(items.len(), ("", head));
+                    return self;
+                }
+            }
+        }
+        Template::build_internal(vec![("", head), ("", self)], "")
+    }
+
+    /// Renders the head dependencies of the template and its child
+    /// templates, each once in the order they are first used, followed
+    /// by a `<style>` with the CSS of scoped styles, see `scoped_css`
+    pub fn head(&self) -> Template {
+        let mut items: Vec<&HeadItem> = vec![];
+        let mut scoped = false;
+        self.visit(&mut |content| match content {
+            TemplateContent::Head(item) if !items.contains(&item) => items.push(item),
+            TemplateContent::ScopedStyle(_) => scoped = true,
+            _ => {}
+        });
+        let tags = TemplateGroup(items.into_iter().map(|item| item.render()).collect());
+        if scoped {
+            let css = self.scoped_css();
+            rhtml! { "{tags}<style>{css}</style>" }
+        } else {
+            rhtml! { "{tags}" }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Hot reloading of `rhtml_file!` templates in development
+//!
+//! Enabled with the `hot-reload` feature. In debug builds, templates
+//! created with `rhtml_file!` are read from disk every time they are
+//! rendered, and validated again at runtime. Release builds always
+//! use the version embedded at compile time.
+//!
+//! Add the livereload endpoint and script to an axum app to refresh
+//! the browser whenever a template file changes:
+//!
+//! ```rust
+//! use axum::{routing::get, Router};
+//! use rust_html::hot_reload::{livereload_handler, livereload_script, LIVERELOAD_PATH};
+//! use rust_html::{rhtml, Template};
+//!
+//! fn app() -> Router {
+//!     Router::new().route(LIVERELOAD_PATH, get(livereload_handler))
+//! }
+//!
+//! fn layout(content: Template) -> Template {
+//!     rhtml! { r#"
+//!         <!DOCTYPE html>
+//!         <html lang="en">
+//!             <head>{livereload_script()}</head>
+//!             <body>{content}</body>
+//!         </html>
+//!     "# }
+//! }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::Stream;
+use rust_html_parser::{
+    prepare_html, read_config, split_placeholders, Placeholder, PreparedHtml, TemplateOptions,
+};
+
+use crate::{Render, SafeUrl, Template, TemplateContent, Unescaped};
+
+/// Path of the livereload endpoint used by `livereload_script`
+pub const LIVERELOAD_PATH: &str = "/_rust_html/livereload";
+
+/// Interval for checking template files for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Template files rendered so far, watched by the livereload endpoint
+static TEMPLATE_FILES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// Script tag reloading the page when a template file changes,
+/// or when the server restarts. Empty in release builds.
+pub fn livereload_script() -> Template {
+    if !cfg!(debug_assertions) {
+        return "".into();
+    }
+    let script = format!(
+        r#"<script>
+(() => {{
+    const source = new EventSource("{}");
+    let reconnecting = false;
+    source.onmessage = () => location.reload();
+    source.onerror = () => {{ reconnecting = true; }};
+    source.onopen = () => {{ if (reconnecting) location.reload(); }};
+}})();
+</script>"#,
+        LIVERELOAD_PATH
+    );
+    Unescaped(script).render()
+}
+
+/// Axum handler for the livereload endpoint at `LIVERELOAD_PATH`
+///
+/// Sends a server-sent event whenever one of the
+/// rendered template files is modified.
+pub async fn livereload_handler() -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = futures_util::stream::unfold(file_times(), |previous| async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let current = file_times();
+            let changed = current
+                .iter()
+                .any(|(path, time)| previous.get(path).is_some_and(|previous| previous != time));
+            if changed {
+                return Some((Ok(Event::default().data("reload")), current));
+            }
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Modification times of all rendered template files
+fn file_times() -> BTreeMap<&'static str, SystemTime> {
+    let files = TEMPLATE_FILES.lock().unwrap_or_else(|err| err.into_inner());
+    files
+        .iter()
+        .filter_map(|path| {
+            let modified = std::fs::metadata(path).and_then(|meta| meta.modified());
+            modified.ok().map(|time| (*path, time))
+        })
+        .collect()
+}
+
+/// Renders the current version of a template file with the current
+/// config of its crate. Falls back to the compiled template if the file
+/// can't be read, and renders the error if the file or config is invalid.
+/// Both are logged with the `log` crate, along with lint warnings.
+pub(crate) fn reload_template(
+    path: &'static str,
+    manifest_dir: &'static str,
+    placeholders: &[(&'static str, bool)],
+    template_parts: Vec<(&'static str, Template)>,
+    template_end: &'static str,
+) -> Template {
+    TEMPLATE_FILES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(path);
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => {
+            log::warn!("failed to reload '{}': {}", path, err);
+            return Template::build_internal(template_parts, template_end);
+        }
+    };
+    let rendered = read_config(std::path::Path::new(manifest_dir))
+        .and_then(|config| render_file(&content, &config.options, placeholders, &template_parts));
+    match rendered {
+        Ok(template) => template,
+        Err(error) => {
+            log::error!("invalid template '{}': {}", path, error);
+            let message = format!("rust_html: invalid template '{}':\n{}", path, error);
+            let escaped = html_escape::encode_safe(&message);
+            Unescaped(format!("<pre>{}</pre>", escaped)).render()
+        }
+    }
+}
+
+/// Validates the template content like the `rhtml_file!` macro does, and
+/// renders it with the compiled placeholders, matched by their source code
+fn render_file(
+    content: &str,
+    options: &TemplateOptions,
+    placeholders: &[(&'static str, bool)],
+    template_parts: &[(&'static str, Template)],
+) -> Result<Template, String> {
+    let (html_parts, sources) = split_placeholders(content)?;
+    let PreparedHtml {
+        html_parts,
+        placeholders: positions,
+        scoped_css,
+        warnings,
+    } = prepare_html(html_parts, options)?;
+    for lint in warnings {
+        log::warn!("{} ({})", lint.message, lint.name);
+    }
+
+    // The HTML parts are owned, so they are added as raw string templates
+    let raw = |html: &str| Template {
+        content: TemplateContent::RawString(html.to_string()),
+    };
+    let mut parts = vec![];
+    // The current `<style scoped>` CSS replaces the compiled one, which
+    // isn't matched below since its placeholder has no source code
+    if let Some(css) = scoped_css {
+        let style = Template {
+            content: TemplateContent::ScopedStyle(css.into()),
+        };
+        parts.push(("", style));
+    }
+    for ((html_part, source), position) in html_parts.iter().zip(&sources).zip(positions) {
+        parts.push(("", raw(html_part)));
+        let source = source.trim();
+        // Values are escaped for the position they were compiled in, so
+        // a source used both in and out of attribute values has two entries
+        let in_attribute = position != Placeholder::Content;
+        let Some(index) = placeholders
+            .iter()
+            .position(|(p, a)| !p.is_empty() && *p == source && *a == in_attribute)
+        else {
+            if placeholders.iter().any(|(p, _)| *p == source) {
+                return Err(format!(
+                    "placeholder '{{{}}}' moved in or out of an attribute value, recompile to use it",
+                    source
+                ));
+            }
+            return Err(format!(
+                "placeholder '{{{}}}' is not in the compiled template, recompile to use it",
+                source
+            ));
+        };
+        let template = template_parts[index].1.clone();
+        if position == Placeholder::UrlAttribute {
+            parts.push(("", SafeUrl(template).render()));
+        } else {
+            parts.push(("", template));
+        }
+    }
+    if let Some(last_part) = html_parts.last() {
+        parts.push(("", raw(last_part)));
+    }
+    Ok(Template::build_internal(parts, ""))
+}
+
+/// Unit tests for hot reloading
+#[cfg(test)]
+mod test_hot_reload {
+    use super::*;
+    use crate::{rhtml, HeadItem};
+
+    const PLACEHOLDERS: [(&str, bool); 3] = [("title", false), ("content", false), ("url", true)];
+
+    fn parts() -> Vec<(&'static str, Template)> {
+        vec![
+            ("<h1>", "Title".into()),
+            ("</h1><p>", "<b>".into()),
+            ("</p><svg><a href=\"", "javascript:alert(1)".render()),
+        ]
+    }
+
+    fn render(content: &str) -> Result<String, String> {
+        render_file(
+            content,
+            &TemplateOptions::default(),
+            &PLACEHOLDERS,
+            &parts(),
+        )
+        .map(String::from)
+    }
+
+    #[test]
+    fn test_render_file() {
+        let html = render("<h1>{title}</h1><p>{ content }</p>");
+        assert_eq!(html.unwrap(), "<h1>Title</h1><p>&lt;b&gt;</p>");
+    }
+
+    #[test]
+    fn test_render_file_changed_markup() {
+        let html = render("<p class=\"{{x}}\">{content}</p><h2>{title}</h2>{title}");
+        assert_eq!(
+            html.unwrap(),
+            "<p class=\"{x}\">&lt;b&gt;</p><h2>Title</h2>Title"
+        );
+    }
+
+    #[test]
+    fn test_render_file_same_source_in_attribute_and_text() {
+        let parts = vec![
+            ("<p title=\"", "<b>".render_attribute()),
+            ("\">", "<b>".render()),
+        ];
+        let placeholders = [("title", true), ("title", false)];
+        let content = "<p title=\"{title}\">{title}</p>";
+        let options = TemplateOptions::default();
+        let template = render_file(content, &options, &placeholders, &parts).unwrap();
+        assert_eq!(
+            String::from(template),
+            String::from(Template::build_internal(parts, "</p>"))
+        );
+    }
+
+    #[test]
+    fn test_render_file_keeps_templates() {
+        let child = rhtml! { "<b>child</b>" }.with_head(HeadItem::Script("/a.js".into()));
+        let parts = vec![("<div>", child)];
+        let options = TemplateOptions::default();
+        let template =
+            render_file("<p>{child}</p>", &options, &[("child", false)], &parts).unwrap();
+        assert_eq!(
+            String::from(template.head()),
+            r#"<script src="&#x2F;a.js"></script>"#
+        );
+        assert_eq!(String::from(template), "<p><b>child</b></p>");
+    }
+
+    #[test]
+    fn test_render_file_scoped_style() {
+        let parts = vec![
+            (
+                "",
+                Template::scoped_style_internal("h1[data-rhtml-0] { color: teal; }"),
+            ),
+            ("<h1 data-rhtml-0>", "Title".into()),
+        ];
+        let placeholders = [("", false), ("title", false)];
+        let content = "<style scoped>h1 {{ color: red; }}</style><h1>{title}</h1>";
+        let options = TemplateOptions::default();
+        let template = render_file(content, &options, &placeholders, &parts).unwrap();
+        let css = String::from(template.scoped_css());
+        assert!(css.contains("color: red") && !css.contains("teal"));
+        let html = String::from(template);
+        assert!(html.starts_with("<h1 data-rhtml-") && html.ends_with(">Title</h1>"));
+    }
+
+    #[test]
+    fn test_render_file_url_attribute() {
+        let html = render("<a href=\"{url}\"></a>");
+        assert_eq!(html.unwrap(), "<a href=\"javascript:alert(1)\"></a>");
+        let html = render("<svg><a href=\"{url}\"></a></svg>");
+        assert_eq!(html.unwrap(), "<svg><a href=\"about:invalid\"></a></svg>");
+        assert!(render("<p>{url}</p>")
+            .unwrap_err()
+            .contains("attribute value"));
+        assert!(render("<p title=\"{title}\"></p>")
+            .unwrap_err()
+            .contains("attribute value"));
+    }
+
+    #[test]
+    fn test_render_file_unknown_placeholder() {
+        let html = render("<h1>{subtitle}</h1>");
+        assert!(html.unwrap_err().contains("{subtitle}"));
+    }
+
+    #[test]
+    fn test_render_file_invalid_html() {
+        let html = render("<div class=></div>{title}");
+        assert!(html.unwrap_err().contains("invalid HTML syntax"));
+        let html = render("<table><tr><td>{title}</td></tr></table>");
+        assert!(html.unwrap_err().contains("fixups"));
+        assert!(render("<path d=\"M0 0\"/><circle r=\"1\"/>").is_ok());
+    }
+
+    #[test]
+    fn test_reload_template() {
+        let path =
+            std::env::temp_dir().join(format!("rust_html_reload_{}.html", std::process::id()));
+        let path: &'static str = Box::leak(path.to_string_lossy().into_owned().into_boxed_str());
+        let reload = || {
+            String::from(reload_template(
+                path,
+                env!("CARGO_MANIFEST_DIR"),
+                &PLACEHOLDERS,
+                parts(),
+                "\"></a></svg>",
+            ))
+        };
+
+        // Missing file falls back to the compiled template
+        assert_eq!(
+            reload(),
+            "<h1>Title</h1><p>&lt;b&gt;</p><svg><a href=\"javascript:alert(1)\"></a></svg>"
+        );
+
+        std::fs::write(path, "<h2>{title}</h2>").unwrap();
+        assert_eq!(reload(), "<h2>Title</h2>");
+
+        std::fs::write(path, "<h2>{missing}</h2>").unwrap();
+        assert!(reload().starts_with("<pre>rust_html: invalid template"));
+
+        assert!(file_times().contains_key(path));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_reload_template_config() {
+        let dir = std::env::temp_dir().join(format!("rust_html_config_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("page.html");
+        std::fs::write(&path, "<div>\n    <img src=\"a.png\">{title}\n</div>").unwrap();
+        let leak = |path: &std::path::Path| -> &'static str {
+            Box::leak(path.to_string_lossy().into_owned().into_boxed_str())
+        };
+        let (path, manifest_dir) = (leak(&path), leak(&dir));
+        let reload = || {
+            String::from(reload_template(
+                path,
+                manifest_dir,
+                &PLACEHOLDERS,
+                parts(),
+                "",
+            ))
+        };
+
+        assert_eq!(reload(), "<div>\n    <img src=\"a.png\">Title\n</div>");
+
+        std::fs::write(dir.join("rust_html.toml"), "minify = true").unwrap();
+        assert_eq!(reload(), "<div>\n<img src=\"a.png\">Title\n</div>");
+
+        std::fs::write(dir.join("rust_html.toml"), "[lints]\nimg_alt = \"deny\"").unwrap();
+        assert!(reload().contains("img_alt"));
+
+        std::fs::write(dir.join("rust_html.toml"), "minify = \"yes\"").unwrap();
+        assert!(reload().contains("invalid rust_html config"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
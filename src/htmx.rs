@@ -0,0 +1,354 @@
+//! htmx support for axum
+//!
+//! Enabled with the `htmx` feature. Provides the `HxRequest` extractor
+//! for htmx request headers, the `HxResponse` type for setting htmx
+//! response headers and `OobResponse` for out-of-band swaps.
+//!
+//! ```rust
+//! use rust_html::htmx::{HxRequest, HxResponse, HxSwap};
+//! use rust_html::{rhtml, Template};
+//!
+//! fn layout(content: Template) -> Template {
+//!     rhtml! { r#"
+//!         <!DOCTYPE html>
+//!         <html lang="en">
+//!             <body>{content}</body>
+//!         </html>
+//!     "# }
+//! }
+//!
+//! async fn contacts(hx: HxRequest) -> Template {
+//!     let list = rhtml! { "<ul><li>Bob</li></ul>" };
+//!     hx.fragment_or_page(list, layout)
+//! }
+//!
+//! async fn save() -> HxResponse {
+//!     HxResponse::new(rhtml! { "<p>Saved!</p>" })
+//!         .trigger("contact-saved")
+//!         .reswap(HxSwap::OuterHtml)
+//! }
+//! ```
+
+use std::convert::Infallible;
+use std::fmt;
+
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use http::request::Parts;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+use crate::integration::HtmlResponse;
+use crate::{Render, Template, TemplateGroup, Unescaped};
+
+const HX_BOOSTED: HeaderName = HeaderName::from_static("hx-boosted");
+const HX_CURRENT_URL: HeaderName = HeaderName::from_static("hx-current-url");
+const HX_REDIRECT: HeaderName = HeaderName::from_static("hx-redirect");
+const HX_REQUEST: HeaderName = HeaderName::from_static("hx-request");
+const HX_RESWAP: HeaderName = HeaderName::from_static("hx-reswap");
+const HX_RETARGET: HeaderName = HeaderName::from_static("hx-retarget");
+const HX_TARGET: HeaderName = HeaderName::from_static("hx-target");
+const HX_TRIGGER: HeaderName = HeaderName::from_static("hx-trigger");
+const HX_TRIGGER_NAME: HeaderName = HeaderName::from_static("hx-trigger-name");
+
+/// Axum extractor for htmx request headers
+///
+/// Extraction never fails, regular requests
+/// simply have `request` set to `false`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct HxRequest {
+    /// `HX-Request`: the request was made by htmx
+    pub request: bool,
+    /// `HX-Boosted`: the request was made by an element using `hx-boost`
+    pub boosted: bool,
+    /// `HX-Target`: id of the target element
+    pub target: Option<String>,
+    /// `HX-Trigger`: id of the triggered element
+    pub trigger: Option<String>,
+    /// `HX-Trigger-Name`: name of the triggered element
+    pub trigger_name: Option<String>,
+    /// `HX-Current-URL`: current URL of the browser
+    pub current_url: Option<String>,
+}
+
+impl HxRequest {
+    /// Reads the htmx request headers from a header map
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let text = |name: &HeaderName| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        let flag = |name: &HeaderName| text(name).as_deref() == Some("true");
+        HxRequest {
+            request: flag(&HX_REQUEST),
+            boosted: flag(&HX_BOOSTED),
+            target: text(&HX_TARGET),
+            trigger: text(&HX_TRIGGER),
+            trigger_name: text(&HX_TRIGGER_NAME),
+            current_url: text(&HX_CURRENT_URL),
+        }
+    }
+
+    /// True if htmx will swap the response into part of the page.
+    /// Boosted requests replace the whole page, so they are not
+    /// fragment requests.
+    pub fn is_fragment_request(&self) -> bool {
+        self.request && !self.boosted
+    }
+
+    /// Returns the fragment for htmx requests, or the fragment
+    /// wrapped in the layout for regular (and boosted) requests
+    pub fn fragment_or_page<F>(&self, fragment: Template, layout: F) -> Template
+    where
+        F: FnOnce(Template) -> Template,
+    {
+        if self.is_fragment_request() {
+            fragment
+        } else {
+            layout(fragment)
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for HxRequest
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(HxRequest::from_headers(&parts.headers))
+    }
+}
+
+/// Swap strategies for `HX-Reswap` (see `hx-swap` in the htmx docs)
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HxSwap {
+    InnerHtml,
+    OuterHtml,
+    BeforeBegin,
+    AfterBegin,
+    BeforeEnd,
+    AfterEnd,
+    Delete,
+    None,
+}
+
+impl HxSwap {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HxSwap::InnerHtml => "innerHTML",
+            HxSwap::OuterHtml => "outerHTML",
+            HxSwap::BeforeBegin => "beforebegin",
+            HxSwap::AfterBegin => "afterbegin",
+            HxSwap::BeforeEnd => "beforeend",
+            HxSwap::AfterEnd => "afterend",
+            HxSwap::Delete => "delete",
+            HxSwap::None => "none",
+        }
+    }
+}
+
+impl fmt::Display for HxSwap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<HxSwap> for HeaderValue {
+    fn from(swap: HxSwap) -> Self {
+        HeaderValue::from_static(swap.as_str())
+    }
+}
+
+/// HTML response with htmx response headers
+///
+/// Wraps an `HtmlResponse`, so status codes and other
+/// headers can be set before converting it.
+#[derive(Debug, Clone)]
+pub struct HxResponse {
+    response: HtmlResponse,
+    triggers: Vec<String>,
+}
+
+impl HxResponse {
+    pub fn new(template: Template) -> Self {
+        HtmlResponse::new(template).into()
+    }
+
+    /// Sets `HX-Redirect`, making htmx do a full page redirect
+    pub fn redirect<V>(mut self, url: V) -> Self
+    where
+        V: TryInto<HeaderValue>,
+        V::Error: fmt::Display,
+    {
+        self.response = self.response.header(HX_REDIRECT, url);
+        self
+    }
+
+    /// Adds an event to `HX-Trigger`. Calling this
+    /// multiple times triggers all events.
+    pub fn trigger(mut self, event: impl Into<String>) -> Self {
+        self.triggers.push(event.into());
+        self
+    }
+
+    /// Sets `HX-Retarget`, a CSS selector replacing the target element
+    pub fn retarget<V>(mut self, selector: V) -> Self
+    where
+        V: TryInto<HeaderValue>,
+        V::Error: fmt::Display,
+    {
+        self.response = self.response.header(HX_RETARGET, selector);
+        self
+    }
+
+    /// Sets `HX-Reswap`, e.g. `HxSwap::OuterHtml` or `"innerHTML swap:1s"`
+    pub fn reswap<V>(mut self, swap: V) -> Self
+    where
+        V: TryInto<HeaderValue>,
+        V::Error: fmt::Display,
+    {
+        self.response = self.response.header(HX_RESWAP, swap);
+        self
+    }
+}
+
+impl From<Template> for HxResponse {
+    fn from(template: Template) -> Self {
+        HxResponse::new(template)
+    }
+}
+
+impl From<HtmlResponse> for HxResponse {
+    fn from(response: HtmlResponse) -> Self {
+        HxResponse {
+            response,
+            triggers: vec![],
+        }
+    }
+}
+
+impl IntoResponse for HxResponse {
+    fn into_response(self) -> Response {
+        let mut response = self.response;
+        if !self.triggers.is_empty() {
+            response = response.header(HX_TRIGGER, self.triggers.join(", "));
+        }
+        response.into_response()
+    }
+}
+
+/// Response with a primary template and out-of-band fragments
+///
+/// Each fragment is tagged with `id` and `hx-swap-oob` attributes on
+/// its root element, so htmx swaps it into the element with that id.
+/// A fragment must consist of a single root element without an `id`,
+/// otherwise the response is an empty `500 Internal Server Error`.
+/// Use `render` to get the error.
+///
+/// ```rust
+/// use rust_html::htmx::{HxSwap, OobResponse};
+/// use rust_html::rhtml;
+///
+/// async fn add_contact() -> OobResponse {
+///     OobResponse::new(rhtml! { "<li>Bob</li>" })
+///         .fragment("contact-count", HxSwap::InnerHtml, rhtml! { "<span>3</span>" })
+///         .fragment("flash", HxSwap::OuterHtml, rhtml! { "<p>Contact added</p>" })
+/// }
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct OobResponse {
+    template: Template,
+    fragments: Vec<Template>,
+    error: Option<String>,
+}
+
+impl OobResponse {
+    pub fn new(template: Template) -> Self {
+        OobResponse {
+            template,
+            fragments: vec![],
+            error: None,
+        }
+    }
+
+    /// Adds an out-of-band fragment swapped into the element with id `target_id`
+    pub fn fragment(mut self, target_id: &str, swap: HxSwap, fragment: Template) -> Self {
+        let html: String = fragment.into();
+        match tag_fragment(&html, target_id, swap) {
+            Ok(tagged) => self.fragments.push(Unescaped(tagged).render()),
+            Err(error) => {
+                self.error.get_or_insert(format!(
+                    "invalid out-of-band fragment for '{}': {}",
+                    target_id, error
+                ));
+            }
+        }
+        self
+    }
+
+    /// Combines the primary template and all fragments
+    pub fn render(self) -> Result<Template, String> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let mut templates = vec![self.template];
+        templates.extend(self.fragments);
+        Ok(TemplateGroup(templates).render())
+    }
+}
+
+impl IntoResponse for OobResponse {
+    fn into_response(self) -> Response {
+        // The error would reveal internals to the client, it's returned
+        // by `render` and in the `Debug` output instead
+        match self.render() {
+            Ok(template) => template.into_response(),
+            Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+impl From<OobResponse> for HxResponse {
+    /// Converts to an `HxResponse` to set htmx headers.
+    /// An invalid response becomes an empty `500 Internal Server Error`.
+    fn from(response: OobResponse) -> Self {
+        match response.render() {
+            Ok(template) => HxResponse::new(template),
+            Err(_) => HtmlResponse::internal_server_error("".into()).into(),
+        }
+    }
+}
+
+/// Validates that the fragment has a single root element,
+/// and adds the `id` and `hx-swap-oob` attributes to it
+fn tag_fragment(html: &str, target_id: &str, swap: HxSwap) -> Result<String, String> {
+    if target_id.is_empty() || target_id.contains(char::is_whitespace) {
+        return Err("target id must be non-empty without whitespace".to_string());
+    }
+    let attributes = rust_html_parser::single_root_attributes(html)?;
+    for existing in ["id", "hx-swap-oob"] {
+        if attributes.iter().any(|attribute| attribute == existing) {
+            return Err(format!(
+                "root element already has an '{}' attribute",
+                existing
+            ));
+        }
+    }
+
+    // The fragment starts with the root element's start tag
+    let rest = &html.trim_start()[1..];
+    let name_len = rest
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(rest.len());
+    Ok(format!(
+        "<{} id=\"{}\" hx-swap-oob=\"{}\"{}",
+        &rest[..name_len],
+        html_escape::encode_double_quoted_attribute(target_id),
+        swap,
+        rest[name_len..].trim_end()
+    ))
+}
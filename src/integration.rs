@@ -1,17 +1,370 @@
+#[cfg(feature = "axum")]
+pub use axum_support::HtmlResponse;
+
 #[cfg(feature = "axum")]
 mod axum_support {
-    use crate::Template;
+    use crate::xml::XmlTemplate;
+    use crate::{AsyncTemplate, Template, TemplateStream};
+    use axum_core::body::Body;
     use axum_core::response::{IntoResponse, Response};
-    use http::{header, HeaderMap, HeaderValue};
+    use bytes::Bytes;
+    use http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+    use http_body::Frame;
+    use std::convert::Infallible;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    fn html_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=utf-8"),
+        );
+        headers
+    }
 
     impl IntoResponse for Template {
         fn into_response(self) -> Response {
-            let mut headers = HeaderMap::new();
-            headers.insert(
+            (html_headers(), self.build()).into_response()
+        }
+    }
+
+    /// Streams the template with chunked transfer encoding,
+    /// flushing each part of the page as soon as it is ready
+    impl IntoResponse for TemplateStream<'static> {
+        fn into_response(self) -> Response {
+            (html_headers(), Body::new(self)).into_response()
+        }
+    }
+
+    impl IntoResponse for AsyncTemplate<'static> {
+        fn into_response(self) -> Response {
+            TemplateStream::from(self).into_response()
+        }
+    }
+
+    /// Responds with the content type of the template,
+    /// e.g. `application/rss+xml` for RSS feeds
+    impl IntoResponse for XmlTemplate {
+        fn into_response(self) -> Response {
+            let content_type = HeaderValue::from_static(self.content_type());
+            ([(header::CONTENT_TYPE, content_type)], String::from(self)).into_response()
+        }
+    }
+
+    /// HTML response with a custom status code and headers
+    ///
+    /// ```rust
+    /// use axum::http::{header, StatusCode};
+    /// use rust_html::{integration::HtmlResponse, rhtml};
+    ///
+    /// let response: HtmlResponse = rhtml! { "<h1>Gone</h1>" }
+    ///     .with_status(StatusCode::GONE)
+    ///     .header(header::ETAG, "\"v1\"")
+    ///     .cache_control("no-store");
+    /// ```
+    ///
+    #[derive(Debug, Clone)]
+    pub struct HtmlResponse {
+        status: StatusCode,
+        headers: HeaderMap,
+        template: Template,
+        header_error: Option<String>,
+    }
+
+    impl HtmlResponse {
+        /// Creates a `200 OK` response
+        pub fn new(template: Template) -> Self {
+            HtmlResponse {
+                status: StatusCode::OK,
+                headers: html_headers(),
+                template,
+                header_error: None,
+            }
+        }
+
+        /// `400 Bad Request` error page
+        pub fn bad_request(template: Template) -> Self {
+            template.with_status(StatusCode::BAD_REQUEST)
+        }
+
+        /// `401 Unauthorized` error page
+        pub fn unauthorized(template: Template) -> Self {
+            template.with_status(StatusCode::UNAUTHORIZED)
+        }
+
+        /// `403 Forbidden` error page
+        pub fn forbidden(template: Template) -> Self {
+            template.with_status(StatusCode::FORBIDDEN)
+        }
+
+        /// `404 Not Found` error page
+        pub fn not_found(template: Template) -> Self {
+            template.with_status(StatusCode::NOT_FOUND)
+        }
+
+        /// `422 Unprocessable Entity` error page, e.g. a form with validation errors
+        pub fn unprocessable_entity(template: Template) -> Self {
+            template.with_status(StatusCode::UNPROCESSABLE_ENTITY)
+        }
+
+        /// `500 Internal Server Error` error page
+        pub fn internal_server_error(template: Template) -> Self {
+            template.with_status(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+
+        /// Sets the status code of the response
+        pub fn status(mut self, status: StatusCode) -> Self {
+            self.status = status;
+            self
+        }
+
+        /// Sets a response header, replacing any previous value.
+        /// An invalid header results in an empty `500 Internal Server Error`.
+        pub fn header<K, V>(mut self, name: K, value: V) -> Self
+        where
+            K: TryInto<HeaderName>,
+            K::Error: std::fmt::Display,
+            V: TryInto<HeaderValue>,
+            V::Error: std::fmt::Display,
+        {
+            let name = match name.try_into() {
+                Ok(name) => name,
+                Err(err) => {
+                    self.header_error = Some(format!("invalid header name: {}", err));
+                    return self;
+                }
+            };
+            match value.try_into() {
+                Ok(value) => {
+                    self.headers.insert(name, value);
+                }
+                Err(err) => {
+                    self.header_error = Some(format!("invalid value for header {}: {}", name, err));
+                }
+            }
+            self
+        }
+
+        /// Sets the `Cache-Control` header, e.g. `"no-store"`
+        /// or `"public, max-age=3600"`
+        pub fn cache_control<V>(self, value: V) -> Self
+        where
+            V: TryInto<HeaderValue>,
+            V::Error: std::fmt::Display,
+        {
+            self.header(header::CACHE_CONTROL, value)
+        }
+    }
+
+    impl Template {
+        /// Creates an `HtmlResponse` with the given status code
+        pub fn with_status(self, status: StatusCode) -> HtmlResponse {
+            HtmlResponse::new(self).status(status)
+        }
+    }
+
+    impl From<Template> for HtmlResponse {
+        fn from(template: Template) -> Self {
+            HtmlResponse::new(template)
+        }
+    }
+
+    impl IntoResponse for HtmlResponse {
+        fn into_response(self) -> Response {
+            // The error would reveal internals to the client, it's only in the `Debug` output
+            if self.header_error.is_some() {
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+            (self.status, self.headers, self.template.build()).into_response()
+        }
+    }
+
+    impl http_body::Body for TemplateStream<'_> {
+        type Data = Bytes;
+        type Error = Infallible;
+
+        fn poll_frame(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+            self.get_mut()
+                .poll_next_chunk(cx)
+                .map(|chunk| chunk.map(|html| Ok(Frame::data(Bytes::from(html)))))
+        }
+    }
+}
+
+#[cfg(feature = "actix-web")]
+mod actix_web_support {
+    use crate::{Render, Template, TemplateGroup};
+    use actix_web::http::header::{self, HeaderValue};
+    use actix_web::http::StatusCode;
+    use actix_web::{HttpRequest, HttpResponse, Responder};
+
+    impl Responder for Template {
+        type Body = String;
+
+        fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+            let mut response = HttpResponse::with_body(StatusCode::OK, self.build());
+            response.headers_mut().insert(
                 header::CONTENT_TYPE,
                 HeaderValue::from_static("text/html; charset=utf-8"),
             );
-            (headers, self.build()).into_response()
+            response
+        }
+    }
+
+    impl Responder for TemplateGroup {
+        type Body = String;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            self.render().respond_to(req)
+        }
+    }
+}
+
+#[cfg(feature = "rocket")]
+mod rocket_support {
+    use crate::Template;
+    use rocket::http::ContentType;
+    use rocket::response::{self, Responder};
+    use rocket::Request;
+
+    impl<'r> Responder<'r, 'static> for Template {
+        fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+            (ContentType::HTML, self.build()).respond_to(request)
+        }
+    }
+}
+
+#[cfg(feature = "warp")]
+mod warp_support {
+    use crate::Template;
+    use warp::reply::{self, Reply, Response};
+
+    impl Reply for Template {
+        fn into_response(self) -> Response {
+            reply::html(self.build()).into_response()
+        }
+    }
+}
+
+#[cfg(feature = "poem")]
+mod poem_support {
+    use crate::Template;
+    use poem::web::Html;
+    use poem::{IntoResponse, Response};
+
+    impl IntoResponse for Template {
+        fn into_response(self) -> Response {
+            Html(self.build()).into_response()
+        }
+    }
+}
+
+#[cfg(feature = "hyper")]
+mod hyper_support {
+    use crate::Template;
+    use bytes::Bytes;
+    use http::{header, HeaderValue, Response};
+    use http_body_util::Full;
+
+    fn html_response<B>(body: B) -> Response<B> {
+        let mut response = Response::new(body);
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=utf-8"),
+        );
+        response
+    }
+
+    impl From<Template> for Response<Full<Bytes>> {
+        fn from(template: Template) -> Self {
+            html_response(Full::from(template.build()))
+        }
+    }
+
+    impl From<Template> for Response<String> {
+        fn from(template: Template) -> Self {
+            html_response(template.build())
+        }
+    }
+}
+
+#[cfg(feature = "sse")]
+pub use sse_support::template_sse;
+
+#[cfg(feature = "sse")]
+mod sse_support {
+    use crate::Template;
+    use axum::response::sse::{Event, Sse};
+    use futures_util::{Stream, StreamExt};
+    use std::convert::Infallible;
+
+    /// Converts a template to a server-sent event.
+    ///
+    /// Multi-line HTML is split over several `data:` fields. Carriage
+    /// returns (which SSE can't transmit) are converted to newlines,
+    /// so `\r\n` and `\r` line endings both become `\n`.
+    impl From<Template> for Event {
+        fn from(template: Template) -> Self {
+            let html = String::from(template)
+                .replace("\r\n", "\n")
+                .replace('\r', "\n");
+            Event::default().data(html)
+        }
+    }
+
+    /// Creates a server-sent events response sending
+    /// each template in the stream as an event
+    ///
+    /// ```rust
+    /// use axum::response::sse::KeepAlive;
+    /// use futures_util::{stream, StreamExt};
+    /// use rust_html::{integration::template_sse, rhtml};
+    ///
+    /// async fn counter() -> impl axum::response::IntoResponse {
+    ///     let updates = stream::iter(0..3).map(|i| rhtml! { "<span>{i}</span>" });
+    ///     template_sse(updates).keep_alive(KeepAlive::default())
+    /// }
+    /// ```
+    pub fn template_sse<S>(
+        stream: S,
+    ) -> Sse<impl Stream<Item = Result<Event, Infallible>> + Send + 'static>
+    where
+        S: Stream<Item = Template> + Send + 'static,
+    {
+        Sse::new(stream.map(|template| Ok(Event::from(template))))
+    }
+}
+
+#[cfg(feature = "websocket")]
+pub use websocket_support::send_templates;
+
+#[cfg(feature = "websocket")]
+mod websocket_support {
+    use crate::Template;
+    use axum::extract::ws::Message;
+    use futures_util::{Sink, SinkExt, Stream, StreamExt};
+
+    impl From<Template> for Message {
+        fn from(template: Template) -> Self {
+            Message::Text(String::from(template).into())
+        }
+    }
+
+    /// Sends each template in the stream as a WebSocket text message,
+    /// e.g. to an axum `WebSocket`. Stops at the first send error.
+    pub async fn send_templates<S, W>(stream: S, socket: &mut W) -> Result<(), W::Error>
+    where
+        S: Stream<Item = Template>,
+        W: Sink<Message> + Unpin,
+    {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(template) = stream.next().await {
+            socket.send(Message::from(template)).await?;
         }
+        Ok(())
     }
 }
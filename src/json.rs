@@ -0,0 +1,74 @@
+//! JSON data for scripts, enabled with the `serde` feature
+//!
+//! `Json` serializes a value for a data island, a `<script>` with JSON
+//! that scripts on the page can read without an extra request:
+//!
+//! ```rust
+//! use rust_html::{rhtml, Json};
+//! use std::collections::BTreeMap;
+//!
+//! let user = BTreeMap::from([("name", "</script><script>alert(1)")]);
+//! let page = rhtml! { r#"
+//!     <script id="user" type="application/json">{Json(&user)}</script>
+//! "# };
+//! assert!(!String::from(page).contains("</script><script>"));
+//! ```
+
+use serde::Serialize;
+
+use crate::{rhtml, Render, Template, TemplateContent};
+
+/// Renders a value as JSON, escaped for the content of a `<script>`
+///
+/// `<`, `>` and `&` are written as `\u003c`, `\u003e` and `\u0026` in
+/// JSON strings, so that the value can't contain `</script>` or `<!--`.
+/// Values that fail to serialize, e.g. maps with non-string keys,
+/// render as `null`.
+///
+/// In a quoted attribute, like `data-state="{Json(&state)}"`, the JSON
+/// is HTML escaped instead, see `Json::attribute`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<T: Serialize> Json<T> {
+    /// Renders the JSON for a quoted attribute value, with HTML escaping
+    pub fn attribute(&self) -> Template {
+        self.to_json().render()
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).unwrap_or_else(|_| "null".to_string())
+    }
+}
+
+impl<T: Serialize> Render for Json<T> {
+    fn render(&self) -> Template {
+        let json = self.to_json();
+        let mut escaped = String::with_capacity(json.len());
+        for c in json.chars() {
+            match c {
+                '<' => escaped.push_str("\\u003c"),
+                '>' => escaped.push_str("\\u003e"),
+                '&' => escaped.push_str("\\u0026"),
+                // Line terminators in JavaScript, but not in JSON
+                '\u{2028}' => escaped.push_str("\\u2028"),
+                '\u{2029}' => escaped.push_str("\\u2029"),
+                c => escaped.push(c),
+            }
+        }
+        Template {
+            content: TemplateContent::RawString(escaped),
+        }
+    }
+
+    fn render_attribute(&self) -> Template {
+        self.attribute()
+    }
+}
+
+/// `<script type="application/ld+json">` with structured data
+/// for search engines, e.g. a schema.org `Article`
+pub fn json_ld<T: Serialize>(value: T) -> Template {
+    let json = Json(value);
+    rhtml! { r#"<script type="application/ld+json">{json}</script>"# }
+}
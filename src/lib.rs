@@ -1,6 +1,28 @@
-pub use rust_html_macros::rhtml;
+pub use rust_html_macros::{asset, rhtml, rhtml_async, rhtml_file, rxml, Form};
 
+use std::borrow::Cow;
+
+// Allows using the macros inside this crate
+extern crate self as rust_html;
+
+pub mod assets;
+mod async_render;
+pub mod form;
+mod head;
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
+#[cfg(feature = "htmx")]
+pub mod htmx;
 pub mod integration;
+#[cfg(feature = "serde")]
+mod json;
+pub mod xml;
+
+pub use assets::Asset;
+pub use async_render::{AsyncRender, AsyncTemplate, RenderFuture, TemplateStream};
+pub use head::HeadItem;
+#[cfg(feature = "serde")]
+pub use json::{json_ld, Json};
 
 /// Struct representing a rust_html template.
 /// Enables easy reusability and injection safety.
@@ -54,6 +76,28 @@ pub struct TemplateGroup(pub Vec<Template>);
 #[derive(Debug, Clone)]
 pub struct Unescaped(pub String);
 
+/// Wrapper to insert a URL into an attribute such as `href`.
+/// URLs with a scheme other than `http`, `https`, `mailto` or
+/// `tel` (e.g. `javascript:`) are replaced with `about:invalid`.
+///
+/// The `rhtml!` macro applies this to `href` and `xlink:href`
+/// placeholders on SVG elements automatically:
+///
+/// ```rust
+/// use rust_html::{rhtml, SafeUrl};
+/// let url = "javascript:alert(1)";
+///
+/// let svg = rhtml! { r#"<svg><a href="{url}">Link</a></svg>"# };
+/// assert_eq!(String::from(svg), r#"<svg><a href="about:invalid">Link</a></svg>"#);
+///
+/// let link = SafeUrl("/home");
+/// let html = rhtml! { r#"<a href="{link}">Home</a>"# };
+/// assert_eq!(String::from(html), r#"<a href="&#x2F;home">Home</a>"#);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct SafeUrl<T>(pub T);
+
 /// Render trait for rust_html templates
 ///
 /// Implement this trait on a struct to create
@@ -61,6 +105,14 @@ pub struct Unescaped(pub String);
 /// can reuse inside other templates.
 pub trait Render {
     fn render(&self) -> Template;
+
+    /// Renders the value inside a quoted attribute value, which the
+    /// `rhtml!` macro uses for placeholders like `<div title="{value}">`.
+    /// Values that are escaped for another context, like `Json` for
+    /// a `<script>`, must escape quotes here.
+    fn render_attribute(&self) -> Template {
+        self.render()
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -70,6 +122,11 @@ enum TemplateContent {
         template_parts: Vec<(&'static str, Template)>,
         template_end: &'static str,
     },
+    /// CSS of a `<style scoped>` block, renders nothing. Owned when
+    /// the block is from a hot reloaded template file.
+    ScopedStyle(Cow<'static, str>),
+    /// Dependency for the page `<head>`, renders nothing
+    Head(HeadItem),
 }
 
 impl Template {
@@ -91,10 +148,133 @@ impl Template {
             },
         }
     }
+    /// Internal macro creation of a rust_html template from a file.
+    ///
+    /// DO NOT USE THIS.
+    /// USE THE `rhtml_file!` MACRO.
+    ///
+    /// With the `hot-reload` feature in debug builds, the file at `path`
+    /// is read again and rendered with the placeholders in `template_parts`
+    /// (identified by their source code in `placeholders`, along with
+    /// whether they were compiled in an attribute value). The config of
+    /// the crate in `manifest_dir` is read again as well.
+    pub fn build_file_internal(
+        path: &'static str,
+        manifest_dir: &'static str,
+        placeholders: &[(&'static str, bool)],
+        template_parts: Vec<(&'static str, Template)>,
+        template_end: &'static str,
+    ) -> Self {
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
+        return hot_reload::reload_template(
+            path,
+            manifest_dir,
+            placeholders,
+            template_parts,
+            template_end,
+        );
+
+        #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+        {
+            let _ = (path, manifest_dir, placeholders);
+            Template::build_internal(template_parts, template_end)
+        }
+    }
+    /// Internal macro creation of the scoped CSS of a template.
+    ///
+    /// DO NOT USE THIS.
+    /// USE `<style scoped>` IN THE `rhtml!` MACRO.
+    ///
+    /// The macro adds this as the first placeholder of a template
+    /// with a scoped style, so that `scoped_css` can find it.
+    pub fn scoped_style_internal(css: &'static str) -> Self {
+        Template {
+            content: TemplateContent::ScopedStyle(Cow::Borrowed(css)),
+        }
+    }
+    /// Collects the CSS of all `<style scoped>` blocks in the template
+    /// and its child templates, each component's CSS included once.
+    ///
+    /// Scoped styles are left out of the rendered HTML. Put the collected
+    /// CSS in the `<head>` of the page:
+    ///
+    /// ```rust
+    /// use rust_html::{rhtml, Template};
+    ///
+    /// fn card(title: &str) -> Template {
+    ///     rhtml! { r#"
+    ///         <style scoped>.title {{ color: teal; }}</style>
+    ///         <div class="card"><h2 class="title">{title}</h2></div>
+    ///     "# }
+    /// }
+    ///
+    /// let content = rhtml! { "<main>{card(\"A\")}{card(\"B\")}</main>" };
+    /// let css = content.scoped_css();
+    /// let page = rhtml! { r#"
+    ///     <!DOCTYPE html>
+    ///     <html lang="en">
+    ///         <head><style>{css}</style></head>
+    ///         <body>{content}</body>
+    ///     </html>
+    /// "# };
+    /// let html = String::from(page);
+    /// assert_eq!(html.matches("color: teal").count(), 1);
+    /// ```
+    ///
+    pub fn scoped_css(&self) -> Template {
+        let mut styles = vec![];
+        self.visit(&mut |content| {
+            if let TemplateContent::ScopedStyle(css) = content {
+                if !styles.contains(&css.as_ref()) {
+                    styles.push(css.as_ref());
+                }
+            }
+        });
+        Template {
+            content: TemplateContent::RawString(styles.concat()),
+        }
+    }
+    /// Calls `visit` with the content of this template
+    /// and its child templates in document order
+    fn visit<'a>(&'a self, visit: &mut impl FnMut(&'a TemplateContent)) {
+        visit(&self.content);
+        if let TemplateContent::WithParameters { template_parts, .. } = &self.content {
+            for (_, template) in template_parts {
+                template.visit(visit);
+            }
+        }
+    }
+    /// Checks the rendered HTML for ids used more than once, and for
+    /// `for`, `aria-labelledby` or `href="#..."` references to missing
+    /// ids. Panics in debug builds, and does nothing in release builds.
+    ///
+    /// The `rhtml!` macro checks static ids within a template at compile
+    /// time. Use this on a fully composed page to check ids rendered by
+    /// placeholders and child components:
+    ///
+    /// ```rust should_panic
+    /// use rust_html::rhtml;
+    /// let field = rhtml! { r#"<input id="name">"# };
+    /// let page = rhtml! { r#"<label for="name">Name</label>{field}{field}"# };
+    /// page.check_ids();
+    /// ```
+    ///
+    pub fn check_ids(&self) {
+        #[cfg(debug_assertions)]
+        {
+            let issues = rust_html_parser::page_id_issues(&self.build());
+            assert!(
+                issues.is_empty(),
+                "Template has invalid ids:\n{}",
+                issues.join("\n")
+            );
+        }
+    }
     /// Internal function. Converts a template to String
     fn build(&self) -> String {
         match &self.content {
             TemplateContent::RawString(value) => value.to_owned(),
+            TemplateContent::ScopedStyle(_) | TemplateContent::Head(_) => String::new(),
             TemplateContent::WithParameters {
                 template_parts,
                 template_end,
@@ -128,17 +308,47 @@ impl Render for Unescaped {
     }
 }
 
+impl<T: Render> Render for SafeUrl<T> {
+    fn render(&self) -> Template {
+        let url = self.0.render().build();
+        let url = if is_safe_url(&url) {
+            url
+        } else {
+            "about:invalid".to_string()
+        };
+        Template {
+            content: TemplateContent::RawString(url),
+        }
+    }
+}
+
+/// Checks that a URL is relative or uses a safe scheme. Browsers
+/// ignore leading whitespace and tabs or newlines in the scheme.
+fn is_safe_url(url: &str) -> bool {
+    let url: String = url
+        .trim_start_matches(|c: char| c.is_ascii_whitespace() || c.is_control())
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let scheme_end = url.find([':', '/', '?', '#']);
+    match scheme_end {
+        Some(index) if url[index..].starts_with(':') => {
+            let scheme = url[..index].to_ascii_lowercase();
+            matches!(scheme.as_str(), "http" | "https" | "mailto" | "tel")
+        }
+        _ => true,
+    }
+}
+
 impl Render for TemplateGroup {
     fn render(&self) -> Template {
-        let string: String = self
+        // Keeps the templates, so that their scoped styles can be collected
+        let template_parts = self
             .0
             .iter()
-            .map(|template| template.build())
-            .collect::<Vec<_>>()
-            .join("");
-        Template {
-            content: TemplateContent::RawString(string),
-        }
+            .map(|template| ("", template.clone()))
+            .collect();
+        Template::build_internal(template_parts, "")
     }
 }
 
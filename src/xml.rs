@@ -0,0 +1,645 @@
+//! XML templates, sitemaps and feeds
+//!
+//! Create XML templates with the `rxml!` macro, or use the typed
+//! builders for `sitemap.xml`, RSS 2.0 and Atom feeds:
+//!
+//! ```rust
+//! use rust_html::xml::{RssFeed, RssItem};
+//!
+//! let feed = RssFeed::new("My blog", "https://example.com", "Latest posts")
+//!     .item(
+//!         RssItem::new("Hello, world!")
+//!             .link("https://example.com/hello")
+//!             .pub_date("Mon, 06 Jan 2025 12:00:00 GMT"),
+//!     )
+//!     .render();
+//! assert_eq!(feed.content_type(), "application/rss+xml; charset=utf-8");
+//! ```
+//!
+//! With the `axum` feature, an `XmlTemplate` can be returned
+//! directly from a handler with its content type.
+
+use crate::{rxml, Template, TemplateContent, Unescaped};
+
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+const RSS_CONTENT_TYPE: &str = "application/rss+xml; charset=utf-8";
+const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+
+/// Struct representing an XML template
+///
+/// Create one using the `rust_html::rxml!` macro:
+///
+/// ```rust
+/// use rust_html::{rxml, xml::XmlTemplate};
+/// let url = "https://example.com/?a=1&b=2";
+/// let template: XmlTemplate = rxml! { "<loc>{url}</loc>" };
+/// assert_eq!(String::from(template), "<loc>https://example.com/?a=1&amp;b=2</loc>");
+/// ```
+///
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct XmlTemplate {
+    template: Template,
+    content_type: &'static str,
+}
+
+/// Render trait for XML templates, see `Render`
+///
+/// Values implementing `Display` are escaped for XML, and
+/// HTML templates are inserted as escaped text.
+pub trait RenderXml {
+    fn render_xml(&self) -> XmlTemplate;
+}
+
+impl XmlTemplate {
+    /// Internal macro creation of an XML template.
+    ///
+    /// DO NOT USE THIS.
+    /// USE THE `rxml!` MACRO.
+    ///
+    /// This implementation is low level and intended
+    /// to be used by the rust_html_macros crate.
+    pub fn build_internal(
+        template_parts: Vec<(&'static str, XmlTemplate)>,
+        template_end: &'static str,
+    ) -> Self {
+        let template_parts = template_parts
+            .into_iter()
+            .map(|(xml_part, param_part)| (xml_part, param_part.template))
+            .collect();
+        XmlTemplate::from_template(Template::build_internal(template_parts, template_end))
+    }
+
+    fn from_template(template: Template) -> Self {
+        XmlTemplate {
+            template,
+            content_type: XML_CONTENT_TYPE,
+        }
+    }
+
+    fn raw(xml: String) -> Self {
+        XmlTemplate::from_template(Template {
+            content: TemplateContent::RawString(xml),
+        })
+    }
+
+    /// Content type used when the template is returned
+    /// as a response, `application/xml` by default
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    /// Sets the content type, e.g. `"image/svg+xml"`
+    pub fn with_content_type(mut self, content_type: &'static str) -> Self {
+        self.content_type = content_type;
+        self
+    }
+}
+
+impl Default for XmlTemplate {
+    fn default() -> Self {
+        XmlTemplate::raw(String::new())
+    }
+}
+
+impl From<XmlTemplate> for String {
+    fn from(value: XmlTemplate) -> Self {
+        value.template.build()
+    }
+}
+
+/// Concatenates XML templates, e.g. a list of feed items
+impl FromIterator<XmlTemplate> for XmlTemplate {
+    fn from_iter<T: IntoIterator<Item = XmlTemplate>>(iter: T) -> Self {
+        XmlTemplate::raw(iter.into_iter().map(String::from).collect())
+    }
+}
+
+impl RenderXml for XmlTemplate {
+    fn render_xml(&self) -> XmlTemplate {
+        self.clone()
+    }
+}
+
+impl RenderXml for Template {
+    fn render_xml(&self) -> XmlTemplate {
+        XmlTemplate::raw(escape_xml(&self.build()))
+    }
+}
+
+impl RenderXml for Unescaped {
+    fn render_xml(&self) -> XmlTemplate {
+        XmlTemplate::raw(self.0.to_owned())
+    }
+}
+
+impl<T> RenderXml for T
+where
+    T: std::fmt::Display,
+{
+    fn render_xml(&self) -> XmlTemplate {
+        XmlTemplate::raw(escape_xml(&self.to_string()))
+    }
+}
+
+/// Escapes text for XML content and attribute values.
+/// Characters that are not allowed in XML 1.0 are removed.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() && (c as u32) < 0x20 => {}
+            '\u{FFFE}' | '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// How often a page in a sitemap is expected to change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeFrequency {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl ChangeFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeFrequency::Always => "always",
+            ChangeFrequency::Hourly => "hourly",
+            ChangeFrequency::Daily => "daily",
+            ChangeFrequency::Weekly => "weekly",
+            ChangeFrequency::Monthly => "monthly",
+            ChangeFrequency::Yearly => "yearly",
+            ChangeFrequency::Never => "never",
+        }
+    }
+}
+
+/// Page in a `Sitemap`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapUrl {
+    location: String,
+    last_modified: Option<String>,
+    change_frequency: Option<ChangeFrequency>,
+    priority: Option<f32>,
+}
+
+impl SitemapUrl {
+    /// Page with its full URL, e.g. `https://example.com/about`
+    pub fn new(location: impl Into<String>) -> Self {
+        SitemapUrl {
+            location: location.into(),
+            last_modified: None,
+            change_frequency: None,
+            priority: None,
+        }
+    }
+
+    /// Date of the last modification in W3C format, e.g. `2025-01-06`
+    pub fn last_modified(mut self, date: impl Into<String>) -> Self {
+        self.last_modified = Some(date.into());
+        self
+    }
+
+    pub fn change_frequency(mut self, change_frequency: ChangeFrequency) -> Self {
+        self.change_frequency = Some(change_frequency);
+        self
+    }
+
+    /// Priority relative to other pages, from `0.0` to `1.0`.
+    /// A NaN or infinite priority is left out.
+    pub fn priority(mut self, priority: f32) -> Self {
+        self.priority = priority.is_finite().then(|| priority.clamp(0.0, 1.0));
+        self
+    }
+
+    fn render(&self) -> XmlTemplate {
+        let location = &self.location;
+        let last_modified = self
+            .last_modified
+            .as_ref()
+            .map(|date| rxml! { "<lastmod>{date}</lastmod>" });
+        let change_frequency = self
+            .change_frequency
+            .map(|frequency| rxml! { "<changefreq>{frequency.as_str()}</changefreq>" });
+        let priority = self.priority.map(|priority| {
+            let priority = format!("{:.1}", priority);
+            rxml! { "<priority>{priority}</priority>" }
+        });
+        rxml! { r#"
+  <url>
+    <loc>{location}</loc>{last_modified.unwrap_or_default()}{change_frequency.unwrap_or_default()}{priority.unwrap_or_default()}
+  </url>"# }
+    }
+}
+
+impl From<&str> for SitemapUrl {
+    fn from(location: &str) -> Self {
+        SitemapUrl::new(location)
+    }
+}
+
+impl From<String> for SitemapUrl {
+    fn from(location: String) -> Self {
+        SitemapUrl::new(location)
+    }
+}
+
+/// Builder for a `sitemap.xml` document
+///
+/// ```rust
+/// use rust_html::xml::{ChangeFrequency, Sitemap, SitemapUrl};
+///
+/// let sitemap = Sitemap::new()
+///     .url("https://example.com/")
+///     .url(SitemapUrl::new("https://example.com/blog").change_frequency(ChangeFrequency::Daily))
+///     .render();
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sitemap {
+    urls: Vec<SitemapUrl>,
+}
+
+impl Sitemap {
+    pub fn new() -> Self {
+        Sitemap::default()
+    }
+
+    /// Adds a page, either a full URL or a `SitemapUrl`
+    pub fn url(mut self, url: impl Into<SitemapUrl>) -> Self {
+        self.urls.push(url.into());
+        self
+    }
+
+    /// Renders the `sitemap.xml` document
+    pub fn render(&self) -> XmlTemplate {
+        let urls: XmlTemplate = self.urls.iter().map(SitemapUrl::render).collect();
+        rxml! { r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">{urls}
+</urlset>
+"# }
+    }
+}
+
+/// Item in an `RssFeed`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RssItem {
+    title: String,
+    link: Option<String>,
+    description: Option<XmlTemplate>,
+    author: Option<String>,
+    guid: Option<String>,
+    pub_date: Option<String>,
+}
+
+impl RssItem {
+    pub fn new(title: impl Into<String>) -> Self {
+        RssItem {
+            title: title.into(),
+            link: None,
+            description: None,
+            author: None,
+            guid: None,
+            pub_date: None,
+        }
+    }
+
+    pub fn link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// Summary or full content of the item. HTML templates
+    /// are escaped, as expected by feed readers.
+    pub fn description(mut self, description: impl RenderXml) -> Self {
+        self.description = Some(description.render_xml());
+        self
+    }
+
+    /// Email address of the author
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Unique identifier of the item, usually its permalink
+    pub fn guid(mut self, guid: impl Into<String>) -> Self {
+        self.guid = Some(guid.into());
+        self
+    }
+
+    /// Publication date in RFC 822 format,
+    /// e.g. `Mon, 06 Jan 2025 12:00:00 GMT`
+    pub fn pub_date(mut self, date: impl Into<String>) -> Self {
+        self.pub_date = Some(date.into());
+        self
+    }
+
+    fn render(&self) -> XmlTemplate {
+        let title = &self.title;
+        let link = self
+            .link
+            .as_ref()
+            .map(|link| rxml! { "<link>{link}</link>" });
+        let description = self
+            .description
+            .clone()
+            .map(|description| rxml! { "<description>{description}</description>" });
+        let author = self
+            .author
+            .as_ref()
+            .map(|author| rxml! { "<author>{author}</author>" });
+        let guid = self
+            .guid
+            .as_ref()
+            .map(|guid| rxml! { "<guid>{guid}</guid>" });
+        let pub_date = self
+            .pub_date
+            .as_ref()
+            .map(|date| rxml! { "<pubDate>{date}</pubDate>" });
+        rxml! { r#"
+    <item>
+      <title>{title}</title>{link.unwrap_or_default()}{description.unwrap_or_default()}{author.unwrap_or_default()}{guid.unwrap_or_default()}{pub_date.unwrap_or_default()}
+    </item>"# }
+    }
+}
+
+/// Builder for an RSS 2.0 feed
+///
+/// Dates use the RFC 822 format, e.g. `Mon, 06 Jan 2025 12:00:00 GMT`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RssFeed {
+    title: String,
+    link: String,
+    description: String,
+    language: Option<String>,
+    self_link: Option<String>,
+    last_build_date: Option<String>,
+    items: Vec<RssItem>,
+}
+
+impl RssFeed {
+    /// Feed with the title, website URL and description of the channel
+    pub fn new(
+        title: impl Into<String>,
+        link: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        RssFeed {
+            title: title.into(),
+            link: link.into(),
+            description: description.into(),
+            language: None,
+            self_link: None,
+            last_build_date: None,
+            items: vec![],
+        }
+    }
+
+    /// Language of the feed, e.g. `en-us`
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// URL of the feed itself, recommended by feed validators
+    pub fn self_link(mut self, url: impl Into<String>) -> Self {
+        self.self_link = Some(url.into());
+        self
+    }
+
+    pub fn last_build_date(mut self, date: impl Into<String>) -> Self {
+        self.last_build_date = Some(date.into());
+        self
+    }
+
+    pub fn item(mut self, item: RssItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Renders the feed with the `application/rss+xml` content type
+    pub fn render(&self) -> XmlTemplate {
+        let title = &self.title;
+        let link = &self.link;
+        let description = &self.description;
+        let self_link = self.self_link.as_ref().map(|url| {
+            rxml! { r#"<atom:link href="{url}" rel="self" type="application/rss+xml"/>"# }
+        });
+        let language = self
+            .language
+            .as_ref()
+            .map(|language| rxml! { "<language>{language}</language>" });
+        let last_build_date = self
+            .last_build_date
+            .as_ref()
+            .map(|date| rxml! { "<lastBuildDate>{date}</lastBuildDate>" });
+        let items: XmlTemplate = self.items.iter().map(RssItem::render).collect();
+        rxml! { r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+  <channel>
+    <title>{title}</title>
+    <link>{link}</link>
+    <description>{description}</description>{self_link.unwrap_or_default()}{language.unwrap_or_default()}{last_build_date.unwrap_or_default()}{items}
+  </channel>
+</rss>
+"# }
+        .with_content_type(RSS_CONTENT_TYPE)
+    }
+}
+
+/// Entry in an `AtomFeed`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomEntry {
+    id: String,
+    title: String,
+    updated: String,
+    link: Option<String>,
+    author: Option<String>,
+    summary: Option<String>,
+    content: Option<XmlTemplate>,
+}
+
+impl AtomEntry {
+    /// Entry with a permanent unique id (usually its URL), title
+    /// and the date it was last updated
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        updated: impl Into<String>,
+    ) -> Self {
+        AtomEntry {
+            id: id.into(),
+            title: title.into(),
+            updated: updated.into(),
+            link: None,
+            author: None,
+            summary: None,
+            content: None,
+        }
+    }
+
+    pub fn link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// Name of the author
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Plain text summary
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// HTML content of the entry
+    pub fn content(mut self, content: Template) -> Self {
+        self.content = Some(content.render_xml());
+        self
+    }
+
+    fn render(&self) -> XmlTemplate {
+        let id = &self.id;
+        let title = &self.title;
+        let updated = &self.updated;
+        let link = self
+            .link
+            .as_ref()
+            .map(|link| rxml! { r#"<link href="{link}"/>"# });
+        let author = self
+            .author
+            .as_ref()
+            .map(|author| rxml! { "<author><name>{author}</name></author>" });
+        let summary = self
+            .summary
+            .as_ref()
+            .map(|summary| rxml! { "<summary>{summary}</summary>" });
+        let content = self
+            .content
+            .clone()
+            .map(|content| rxml! { r#"<content type="html">{content}</content>"# });
+        rxml! { r#"
+  <entry>
+    <id>{id}</id>
+    <title>{title}</title>
+    <updated>{updated}</updated>{link.unwrap_or_default()}{author.unwrap_or_default()}{summary.unwrap_or_default()}{content.unwrap_or_default()}
+  </entry>"# }
+    }
+}
+
+/// Builder for an Atom feed
+///
+/// Dates use the RFC 3339 format, e.g. `2025-01-06T12:00:00Z`.
+///
+/// ```rust
+/// use rust_html::{rhtml, xml::{AtomEntry, AtomFeed}};
+///
+/// let feed = AtomFeed::new("https://example.com/", "My blog", "2025-01-06T12:00:00Z")
+///     .entry(
+///         AtomEntry::new("https://example.com/hello", "Hello!", "2025-01-06T12:00:00Z")
+///             .content(rhtml! { "<p>First post</p>" }),
+///     )
+///     .render();
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomFeed {
+    id: String,
+    title: String,
+    updated: String,
+    link: Option<String>,
+    self_link: Option<String>,
+    author: Option<String>,
+    entries: Vec<AtomEntry>,
+}
+
+impl AtomFeed {
+    /// Feed with a permanent unique id (usually the website URL),
+    /// title and the date it was last updated
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        updated: impl Into<String>,
+    ) -> Self {
+        AtomFeed {
+            id: id.into(),
+            title: title.into(),
+            updated: updated.into(),
+            link: None,
+            self_link: None,
+            author: None,
+            entries: vec![],
+        }
+    }
+
+    /// URL of the website
+    pub fn link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// URL of the feed itself
+    pub fn self_link(mut self, url: impl Into<String>) -> Self {
+        self.self_link = Some(url.into());
+        self
+    }
+
+    /// Name of the author, required unless every entry has one
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    pub fn entry(mut self, entry: AtomEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Renders the feed with the `application/atom+xml` content type
+    pub fn render(&self) -> XmlTemplate {
+        let id = &self.id;
+        let title = &self.title;
+        let updated = &self.updated;
+        let link = self
+            .link
+            .as_ref()
+            .map(|link| rxml! { r#"<link href="{link}"/>"# });
+        let self_link = self
+            .self_link
+            .as_ref()
+            .map(|url| rxml! { r#"<link href="{url}" rel="self"/>"# });
+        let author = self
+            .author
+            .as_ref()
+            .map(|author| rxml! { "<author><name>{author}</name></author>" });
+        let entries: XmlTemplate = self.entries.iter().map(AtomEntry::render).collect();
+        rxml! { r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>{id}</id>
+  <title>{title}</title>
+  <updated>{updated}</updated>{link.unwrap_or_default()}{self_link.unwrap_or_default()}{author.unwrap_or_default()}{entries}
+</feed>
+"# }
+        .with_content_type(ATOM_CONTENT_TYPE)
+    }
+}